@@ -0,0 +1,51 @@
+// Compiles the bank shipped with the app into the fallback questions, so they never drift from it.
+// The build fails on any row that the app would skip when loading the bank, checked with the app's own rules.
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/csv_rows.rs"]
+mod csv_rows;
+
+const BANK: &str = "static/questions.csv";
+
+// Optional texts as Rust code
+fn optional(text: &Option<String>) -> String {
+    match text {
+        Some(text) => format!("Some({:?}.to_string())", text),
+        None => "None".to_string(),
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", BANK);
+    println!("cargo:rerun-if-changed=src/csv_rows.rs");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let csv_data = fs::read_to_string(BANK).unwrap_or_else(|e| panic!("cannot read {}: {}", BANK, e));
+    let (rows, issues) = csv_rows::parse_rows(&csv_data);
+    if !issues.is_empty() {
+        let issues = issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>();
+        panic!("{} has invalid rows:\n{}", BANK, issues.join("\n"));
+    }
+
+    let mut questions = String::new();
+    for row in rows {
+        let options = row.options.iter().map(|option| format!("{:?}.to_string()", option)).collect::<Vec<_>>();
+        writeln!(
+            questions,
+            "    Question {{ id: {}, text: {:?}.to_string(), options: vec![{}], correct_answer_index: {}, explanation: {}, hint: {}, media: None }},",
+            row.id,
+            row.question,
+            options.join(", "),
+            row.correct_answer_index,
+            optional(&row.explanation),
+            optional(&row.hint),
+        )
+        .unwrap();
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("fallback_questions.rs");
+    fs::write(out, format!("vec![\n{}]\n", questions)).unwrap();
+}
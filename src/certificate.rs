@@ -0,0 +1,133 @@
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use crate::models::{console_log, sleep};
+use crate::session::QuizSession;
+
+// How long the URL of a downloaded file is kept, the download has started long before
+const DOWNLOAD_URL_LIFETIME_MS: i32 = 40_000;
+
+// Short code printed on the certificate, derived from the quiz drawn and the answers given,
+// so a certificate can be checked against the game it claims to come from
+pub fn verification_code(session: &QuizSession) -> String {
+    // FNV-1a, stable across builds and platforms unlike the standard hasher
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |value: u64| {
+        for byte in value.to_le_bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    feed(session.seed);
+    for (question, progress) in session.questions.iter().zip(&session.progress) {
+        feed(question.id as u64);
+        feed(progress.first_answer().map_or(u64::MAX, |a| a as u64));
+    }
+
+    let code = format!("{:012X}", hash >> 16);
+    format!("{}-{}-{}", &code[0..4], &code[4..8], &code[8..12])
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn certificate_svg(name: &str, title: &str, session: &QuizSession) -> String {
+    let date = js_sys::Date::new_0().to_locale_date_string("fr-FR", &JsValue::UNDEFINED);
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="1123" height="794" viewBox="0 0 1123 794">
+  <rect width="1123" height="794" fill="#ffffff"/>
+  <rect x="30" y="30" width="1063" height="734" fill="none" stroke="#3b82f6" stroke-width="8"/>
+  <rect x="50" y="50" width="1023" height="694" fill="none" stroke="#f59e0b" stroke-width="2"/>
+  <g font-family="Georgia, serif" text-anchor="middle" fill="#1f2937">
+    <text x="561" y="170" font-size="56" fill="#3b82f6">Certificat de réussite</text>
+    <text x="561" y="260" font-size="24">décerné à</text>
+    <text x="561" y="340" font-size="48" font-weight="bold">{name}</text>
+    <text x="561" y="420" font-size="24">pour avoir réussi le quiz</text>
+    <text x="561" y="470" font-size="32">« {title} »</text>
+    <text x="561" y="550" font-size="28">avec un score de {score} / {total} ({percentage} %)</text>
+    <text x="561" y="650" font-size="20" fill="#6b7280">Le {date}</text>
+    <text x="561" y="690" font-size="16" fill="#6b7280">Code de vérification : {code}</text>
+  </g>
+</svg>
+"##,
+        name = escape_xml(name),
+        title = escape_xml(title),
+        score = session.score(),
+        total = session.len(),
+        percentage = session.percentage(),
+        date = String::from(date),
+        code = verification_code(session),
+    )
+}
+
+// Hand a file built in the app to the browser as a download
+pub fn download(filename: &str, contents: &str, mime_type: &str) {
+    if let Err(e) = try_download(filename, contents, mime_type) {
+        console_log(&format!("Could not download {}: {:?}", filename, e));
+    }
+}
+
+fn try_download(filename: &str, contents: &str, mime_type: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let link = document.create_element("a")?.dyn_into::<HtmlAnchorElement>()?;
+    link.set_href(&url);
+    link.set_download(filename);
+    link.click();
+    // Revoked right after the click, the URL can be gone before Firefox and Safari start the download
+    spawn_local(async move {
+        sleep(DOWNLOAD_URL_LIFETIME_MS).await;
+        let _ = Url::revoke_object_url(&url);
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Question;
+
+    fn session(seed: u64, first_answer: usize) -> QuizSession {
+        let questions = (0..3)
+            .map(|id| Question {
+                id,
+                text: format!("Question {}", id),
+                options: vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()],
+                correct_answer_index: 0,
+                explanation: None,
+                hint: None,
+                media: None,
+            })
+            .collect();
+        let mut session = QuizSession::new(questions);
+        session.seed = seed;
+        session.select(first_answer);
+        session.submit();
+        session
+    }
+
+    // Printed certificates must still check out after the app is rebuilt
+    #[test]
+    fn verification_code_does_not_change() {
+        assert_eq!(verification_code(&session(42, 0)), "AFD8-E8DB-B0D6");
+    }
+
+    #[test]
+    fn verification_code_depends_on_the_quiz_and_the_answers() {
+        let code = verification_code(&session(42, 0));
+        assert_ne!(verification_code(&session(43, 0)), code);
+        assert_ne!(verification_code(&session(42, 1)), code);
+    }
+}
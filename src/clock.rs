@@ -0,0 +1,21 @@
+// Current time in milliseconds, read from the browser. Native builds (the unit tests) use a clock
+// set by hand instead, so time-dependent code can be checked without a browser.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    static NOW: std::cell::Cell<f64> = const { std::cell::Cell::new(0.0) };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    NOW.with(|now| now.get())
+}
+
+#[cfg(test)]
+pub fn set_now(ms: f64) {
+    NOW.with(|now| now.set(ms));
+}
@@ -0,0 +1,32 @@
+use std::collections::HashSet;
+use yew::prelude::*;
+use crate::formats::BankIssue;
+
+#[derive(Properties, PartialEq)]
+pub struct BankReportProps {
+    pub issues: Vec<BankIssue>,
+}
+
+// Rows of the bank left out when loading it, so its author can fix them
+#[function_component(BankReport)]
+pub fn bank_report(props: &BankReportProps) -> Html {
+    if props.issues.is_empty() {
+        return html! {};
+    }
+
+    let skipped = props.issues.iter().map(|issue| issue.line).collect::<HashSet<_>>().len();
+    html! {
+        <details class="bank-report">
+            <summary>
+                { if skipped > 1 {
+                    format!("{} lignes de la banque de questions ont été ignorées", skipped)
+                } else {
+                    "1 ligne de la banque de questions a été ignorée".to_string()
+                } }
+            </summary>
+            <ul>
+                { for props.issues.iter().map(|issue| html! { <li>{ issue.to_string() }</li> }) }
+            </ul>
+        </details>
+    }
+}
@@ -0,0 +1,43 @@
+use yew::prelude::*;
+use crate::session::CalibrationRow;
+
+#[derive(Properties, PartialEq)]
+pub struct CalibrationProps {
+    pub rows: Vec<CalibrationRow>,
+}
+
+// How often the player was right at each confidence level they picked
+#[function_component(CalibrationSummary)]
+pub fn calibration_summary(props: &CalibrationProps) -> Html {
+    html! {
+        <div class="calibration">
+            <h3>{ "Calibration de votre confiance" }</h3>
+            <table>
+                <thead>
+                    <tr>
+                        <th>{ "Confiance" }</th>
+                        <th>{ "Réponses" }</th>
+                        <th>{ "Correctes" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        props.rows.iter().map(|row| {
+                            let rate = match (row.correct * 100).checked_div(row.answered) {
+                                Some(percentage) => format!("{} ({}%)", row.correct, percentage),
+                                None => "—".to_string(),
+                            };
+                            html! {
+                                <tr>
+                                    <td>{ row.confidence.label() }</td>
+                                    <td>{ row.answered }</td>
+                                    <td>{ rate }</td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,53 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use crate::certificate::{certificate_svg, download, verification_code};
+use crate::session::QuizSession;
+
+#[derive(Properties, PartialEq)]
+pub struct CertificateProps {
+    pub session: QuizSession,
+    pub quiz_title: AttrValue,
+}
+
+// Offered on the results of a passed quiz
+#[function_component(CertificatePanel)]
+pub fn certificate_panel(props: &CertificateProps) -> Html {
+    let name = use_state(String::new);
+
+    let on_name_input = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_download = {
+        let name = name.clone();
+        let session = props.session.clone();
+        let quiz_title = props.quiz_title.clone();
+        Callback::from(move |_| {
+            let svg = certificate_svg(name.trim(), &quiz_title, &session);
+            download("certificat-quiz.svg", &svg, "image/svg+xml");
+        })
+    };
+
+    html! {
+        <div class="share-panel certificate-panel">
+            <h3>{ "🎓 Certificat de réussite" }</h3>
+            <p class="share-note">
+                { format!("Vous avez dépassé les {} % requis, votre certificat vous attend.", props.session.pass_mark()) }
+            </p>
+            <label class="challenge-name">
+                { "Nom à faire figurer sur le certificat" }
+                <input type="text" value={(*name).clone()} oninput={on_name_input} maxlength="60" />
+            </label>
+            <div class="share-actions">
+                <button class="submit-btn" onclick={on_download} disabled={name.trim().is_empty()}>
+                    { "Télécharger le certificat" }
+                </button>
+            </div>
+            <p class="certificate-code">{ format!("Code de vérification : {}", verification_code(&props.session)) }</p>
+        </div>
+    }
+}
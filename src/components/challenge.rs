@@ -0,0 +1,59 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::components::PackMismatch;
+use crate::models::Question;
+use crate::packs::{linked_pack_id, Pack};
+use crate::routes::Route;
+use crate::session::{QuizMode, QuizSession};
+use crate::share::Challenge;
+
+#[derive(Properties, PartialEq)]
+pub struct ChallengeProps {
+    pub questions: Vec<Question>,
+    // The pack the questions come from, which must be the one of the challenge
+    pub loaded_pack: String,
+    pub packs: Vec<Pack>,
+    pub on_load_pack: Callback<String>,
+    pub on_accept: Callback<QuizSession>,
+}
+
+// Landing page of a challenge link: who sent it and the score to beat
+#[function_component(ChallengePage)]
+pub fn challenge_page(props: &ChallengeProps) -> Html {
+    let challenge = use_location().and_then(|location| location.query::<Challenge>().ok());
+    if let Some(challenge) = &challenge {
+        let pack = linked_pack_id(&challenge.pack, &props.packs);
+        if pack != props.loaded_pack {
+            return html! { <PackMismatch pack={pack} packs={props.packs.clone()} on_load_pack={props.on_load_pack.clone()} /> };
+        }
+    }
+    let session = challenge.as_ref().and_then(|challenge| challenge.to_session(&props.questions));
+
+    let (Some(challenge), Some(session)) = (challenge, session) else {
+        return html! {
+            <div class="error-container">
+                <p>{ "Ce défi est invalide ou porte sur des questions qui ne sont plus disponibles." }</p>
+                <Link<Route> to={Route::Home}>{ "Choisir un mode de jeu" }</Link<Route>>
+            </div>
+        };
+    };
+
+    let score = session.opponent_score().unwrap_or_default();
+    let description = match session.mode {
+        QuizMode::Classic => format!("Quiz classique de {} questions", session.len()),
+        QuizMode::Practice => format!("Entraînement de {} questions", session.len()),
+        QuizMode::Survival => "Mode survie sur les mêmes questions".to_string(),
+    };
+    let on_accept = props.on_accept.reform(move |_| session.clone());
+
+    html! {
+        <div class="result-container">
+            <h2>{ format!("{} vous défie !", challenge.sender()) }</h2>
+            <p>{ description }</p>
+            <div class="score">{ score } <span>{ " bonne(s) réponse(s) à battre" }</span></div>
+            <button class="retry-btn" onclick={on_accept}>
+                { "Relever le défi" }
+            </button>
+        </div>
+    }
+}
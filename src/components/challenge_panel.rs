@@ -0,0 +1,67 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use crate::components::QrCodeSvg;
+use crate::share::{copy_to_clipboard, Challenge};
+
+#[derive(Properties, PartialEq)]
+pub struct ChallengePanelProps {
+    pub challenge: Challenge,
+    pub score: usize,
+}
+
+#[function_component(ChallengePanel)]
+pub fn challenge_panel(props: &ChallengePanelProps) -> Html {
+    let name = use_state(String::new);
+    let show_qr = use_state(|| false);
+    let copied = use_state(|| false);
+
+    let mut challenge = props.challenge.clone();
+    challenge.name = (*name).clone();
+    let url = challenge.url();
+
+    let on_name_input = {
+        let name = name.clone();
+        let copied = copied.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+            copied.set(false);
+        })
+    };
+
+    let on_copy = {
+        let copied = copied.clone();
+        let text = format!("Je te défie au Quiz de Culture Générale ! Score à battre : {}. {}", props.score, url);
+        Callback::from(move |_| {
+            copy_to_clipboard(text.clone());
+            copied.set(true);
+        })
+    };
+
+    let on_toggle_qr = {
+        let show_qr = show_qr.clone();
+        Callback::from(move |_| show_qr.set(!*show_qr))
+    };
+
+    html! {
+        <div class="share-panel">
+            <h3>{ "Défier un ami" }</h3>
+            <p class="share-note">{ "Votre ami jouera exactement les mêmes questions, puis comparera ses réponses aux vôtres." }</p>
+            <label class="challenge-name">
+                { "Votre prénom (facultatif)" }
+                <input type="text" value={(*name).clone()} oninput={on_name_input} maxlength="30" />
+            </label>
+            <input class="share-link" type="text" readonly=true value={url.clone()} aria-label="Lien du défi" />
+            <div class="share-actions">
+                <button class="skip-btn" onclick={on_copy}>{ "Copier le défi" }</button>
+                <button class="skip-btn" onclick={on_toggle_qr} aria-expanded={show_qr.to_string()}>{ "QR code" }</button>
+            </div>
+            if *show_qr {
+                <QrCodeSvg data={url} label="QR code du défi" />
+            }
+            <p class="share-status" role="status" aria-live="polite">
+                { if *copied { "Défi copié !" } else { "" } }
+            </p>
+        </div>
+    }
+}
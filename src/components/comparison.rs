@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use yew::prelude::*;
+use crate::session::QuizSession;
+
+#[derive(Properties, PartialEq)]
+pub struct ComparisonProps {
+    pub session: QuizSession,
+}
+
+fn answer_cell(answer: Option<&String>, correct: bool) -> Html {
+    let class = if correct { "comparison-answer correct" } else { "comparison-answer incorrect" };
+    html! {
+        <td class={class}>
+            { if correct { "✓ " } else { "✗ " } }
+            { answer.map(|a| a.as_str()).unwrap_or("Sans réponse") }
+        </td>
+    }
+}
+
+// Side-by-side answers of the player and the opponent of a challenge
+#[function_component(ChallengeComparison)]
+pub fn challenge_comparison(props: &ComparisonProps) -> Html {
+    let session = &props.session;
+    let (Some(opponent), Some(opponent_score)) = (&session.opponent, session.opponent_score()) else {
+        return html! {};
+    };
+
+    let score = session.score();
+    let verdict = match score.cmp(&opponent_score) {
+        Ordering::Greater => "Vous avez gagné le défi !",
+        Ordering::Less => "Défi perdu, retentez votre chance !",
+        Ordering::Equal => "Égalité parfaite !",
+    };
+
+    html! {
+        <div class="comparison-container">
+            <h3>{ format!("Vous contre {}", opponent.name) }</h3>
+            <p class="comparison-verdict">{ format!("{} — {} : {}", score, opponent_score, verdict) }</p>
+            <table class="comparison-table">
+                <thead>
+                    <tr>
+                        <th>{ "Question" }</th>
+                        <th>{ "Vous" }</th>
+                        <th>{ opponent.name.clone() }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        (0..session.len()).map(|i| {
+                            let question = &session.questions[i];
+                            let mine = session.progress[i].first_answer().and_then(|a| question.options.get(a));
+                            let theirs = opponent
+                                .answers
+                                .get(i)
+                                .copied()
+                                .flatten()
+                                .and_then(|a| question.options.get(a));
+                            html! {
+                                <tr>
+                                    <td>{ &question.text }</td>
+                                    { answer_cell(mine, session.is_correct(i)) }
+                                    { answer_cell(theirs, session.opponent_is_correct(i) == Some(true)) }
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
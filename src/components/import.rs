@@ -0,0 +1,128 @@
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{DragEvent, File, HtmlInputElement};
+use yew::prelude::*;
+use crate::error::LoadError;
+use crate::formats::{parse_bank, BankFormat, QuestionBank};
+use crate::models::console_log;
+
+#[derive(Properties, PartialEq)]
+pub struct ImportProps {
+    // The file name and the questions read from it
+    pub on_import: Callback<(String, QuestionBank)>,
+}
+
+// Read and parse a bank file in the browser, nothing is sent anywhere
+async fn read_bank(file: File) -> Result<QuestionBank, String> {
+    let text = JsFuture::from(file.text())
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .ok_or_else(|| format!("Impossible de lire {}.", file.name()))?;
+    let content_type = file.type_();
+    let format = BankFormat::detect(&file.name(), Some(content_type.as_str()).filter(|t| !t.is_empty()));
+    let bank = parse_bank(&file.name(), &text, format).map_err(|e| e.to_string())?;
+    if bank.questions.is_empty() {
+        return Err(LoadError::InsufficientQuestions {
+            source: file.name(),
+            found: 0,
+            needed: 1,
+        }
+        .to_string());
+    }
+    Ok(bank)
+}
+
+// Drop zone and file picker to play a bank file from the player's device
+#[function_component(BankImport)]
+pub fn bank_import(props: &ImportProps) -> Html {
+    let dragging = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let import = {
+        let error = error.clone();
+        let on_import = props.on_import.clone();
+        Callback::from(move |file: File| {
+            let error = error.clone();
+            let on_import = on_import.clone();
+            spawn_local(async move {
+                let name = file.name();
+                match read_bank(file).await {
+                    Ok(bank) => {
+                        console_log(&format!("Imported {} questions from {}", bank.questions.len(), name));
+                        error.set(None);
+                        on_import.emit((name, bank));
+                    }
+                    Err(e) => {
+                        console_log(&format!("Error importing {}: {}", name, e));
+                        error.set(Some(e));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_change = {
+        let import = import.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                import.emit(file);
+            }
+            // Picking the same file again after fixing it should import it again
+            input.set_value("");
+        })
+    };
+
+    // Dropping is only allowed when dragover is cancelled
+    let on_drag_over = {
+        let dragging = dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(true);
+        })
+    };
+
+    let on_drag_leave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| dragging.set(false))
+    };
+
+    let on_drop = {
+        let dragging = dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(false);
+            let file = e
+                .data_transfer()
+                .and_then(|transfer| transfer.files())
+                .and_then(|files| files.get(0));
+            if let Some(file) = file {
+                import.emit(file);
+            }
+        })
+    };
+
+    html! {
+        <div
+            class={classes!("import-zone", dragging.then_some("dragging"))}
+            ondragover={on_drag_over}
+            ondragleave={on_drag_leave}
+            ondrop={on_drop}
+        >
+            <p>{ "Vous avez vos propres questions ? Déposez un fichier CSV, JSON ou TOML ici, ou" }</p>
+            <label class="skip-btn import-btn">
+                { "Choisir un fichier" }
+                <input
+                    class="sr-only"
+                    type="file"
+                    accept=".csv,.json,.toml,text/csv,application/json"
+                    onchange={on_change}
+                />
+            </label>
+            <p class="import-note">{ "Le fichier est lu sur votre appareil, il n'est envoyé nulle part." }</p>
+            if let Some(error) = &*error {
+                <p class="loading-error" role="alert">{ error }</p>
+            }
+        </div>
+    }
+}
@@ -1,3 +1,39 @@
+pub mod bank_report;
+pub mod calibration;
+pub mod certificate;
+pub mod challenge;
+pub mod challenge_panel;
+pub mod comparison;
+pub mod import;
+pub mod navigator;
+pub mod pack_mismatch;
+pub mod qr_code;
 pub mod question;
+pub mod results;
+pub mod review;
+pub mod settings;
+pub mod share_panel;
+pub mod shared;
+pub mod shortcut_help;
+pub mod start;
+pub mod stats;
 
+pub use bank_report::BankReport;
+pub use calibration::CalibrationSummary;
+pub use certificate::CertificatePanel;
+pub use challenge::ChallengePage;
+pub use challenge_panel::ChallengePanel;
+pub use comparison::ChallengeComparison;
+pub use import::BankImport;
+pub use navigator::QuestionNavigator;
+pub use pack_mismatch::PackMismatch;
+pub use qr_code::QrCodeSvg;
 pub use question::QuestionComponent;
+pub use results::ResultsScreen;
+pub use review::ResultsReview;
+pub use settings::SettingsPage;
+pub use share_panel::SharePanel;
+pub use shared::SharedResultPage;
+pub use shortcut_help::ShortcutHelp;
+pub use start::StartScreen;
+pub use stats::StatsPage;
@@ -0,0 +1,49 @@
+use yew::prelude::*;
+use crate::session::QuestionStatus;
+
+#[derive(Properties, PartialEq)]
+pub struct NavigatorProps {
+    pub statuses: Vec<QuestionStatus>,
+    pub current: usize,
+    pub on_jump: Callback<usize>,
+}
+
+#[function_component(QuestionNavigator)]
+pub fn question_navigator(props: &NavigatorProps) -> Html {
+    html! {
+        <nav class="question-navigator" aria-label="Navigation entre les questions">
+            {
+                props.statuses.iter().enumerate().map(|(index, status)| {
+                    let (state_class, label) = match status {
+                        QuestionStatus::Pending => ("", "Non répondue"),
+                        QuestionStatus::Answered => ("answered", "Répondue"),
+                        QuestionStatus::Skipped => ("skipped", "Passée"),
+                        QuestionStatus::Flagged => ("flagged", "Marquée pour révision"),
+                    };
+                    let class = classes!(
+                        "nav-item",
+                        state_class,
+                        (index == props.current).then_some("current")
+                    );
+
+                    let on_click = {
+                        let on_jump = props.on_jump.clone();
+                        Callback::from(move |_| on_jump.emit(index))
+                    };
+
+                    html! {
+                        <button
+                            class={class}
+                            onclick={on_click}
+                            title={format!("Question {} : {}", index + 1, label)}
+                            aria-label={format!("Question {} : {}", index + 1, label)}
+                            aria-current={(index == props.current).then_some("step")}
+                        >
+                            { index + 1 }
+                        </button>
+                    }
+                }).collect::<Html>()
+            }
+        </nav>
+    }
+}
@@ -0,0 +1,37 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::packs::Pack;
+use crate::routes::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct PackMismatchProps {
+    // ID of the pack the link was made with
+    pub pack: String,
+    pub packs: Vec<Pack>,
+    pub on_load_pack: Callback<String>,
+}
+
+// Shown instead of a share or challenge link made with another pack than the one loaded: its seed and
+// question IDs would pick other questions from this bank
+#[function_component(PackMismatch)]
+pub fn pack_mismatch(props: &PackMismatchProps) -> Html {
+    let listed = props.packs.iter().find(|pack| pack.id == props.pack);
+    let title = listed.map_or(props.pack.as_str(), |pack| pack.title.as_str());
+
+    html! {
+        <div class="error-container">
+            <p>{ format!("Ce quiz porte sur le thème « {} », qui n'est pas celui chargé.", title) }</p>
+            if listed.is_some() {
+                <button class="submit-btn" onclick={props.on_load_pack.reform({
+                    let pack = props.pack.clone();
+                    move |_| pack.clone()
+                })}>
+                    { "Charger ce thème" }
+                </button>
+            } else {
+                <p>{ "Ce thème n'est pas disponible ici." }</p>
+            }
+            <Link<Route> to={Route::Home}>{ "Choisir un mode de jeu" }</Link<Route>>
+        </div>
+    }
+}
@@ -0,0 +1,36 @@
+use qrcodegen::{QrCode, QrCodeEcc};
+use yew::prelude::*;
+
+// Blank modules around the code, as scanners expect
+const QUIET_ZONE: i32 = 4;
+
+#[derive(Properties, PartialEq)]
+pub struct QrCodeProps {
+    pub data: String,
+    #[prop_or_default]
+    pub label: AttrValue,
+}
+
+// QR code generated in the bundle itself, so it works offline
+#[function_component(QrCodeSvg)]
+pub fn qr_code_svg(props: &QrCodeProps) -> Html {
+    let Ok(qr) = QrCode::encode_text(&props.data, QrCodeEcc::Medium) else {
+        return html! { <p class="qr-error">{ "Lien trop long pour un QR code." }</p> };
+    };
+
+    // One square per dark module, all in a single path
+    let size = qr.size();
+    let path = (0..size)
+        .flat_map(|y| (0..size).map(move |x| (x, y)))
+        .filter(|&(x, y)| qr.get_module(x, y))
+        .map(|(x, y)| format!("M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE))
+        .collect::<String>();
+    let view_box = format!("0 0 {0} {0}", size + QUIET_ZONE * 2);
+
+    html! {
+        <svg class="qr-code" viewBox={view_box} role="img" aria-label={props.label.clone()} shape-rendering="crispEdges">
+            <rect width="100%" height="100%" fill="#ffffff" />
+            <path d={path} fill="#000000" />
+        </svg>
+    }
+}
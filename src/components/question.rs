@@ -1,5 +1,8 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
 use yew::prelude::*;
-use crate::models::Question;
+use crate::models::{MediaKind, Question};
+use crate::session::Confidence;
 
 #[derive(Properties, PartialEq)]
 pub struct QuestionProps {
@@ -7,6 +10,26 @@ pub struct QuestionProps {
     pub selected_option: Option<usize>,
     pub is_submitted: bool,
     pub on_select: Callback<usize>,
+    // Options taken away by the 50/50 lifeline, hidden until the answer is revealed
+    #[prop_or_default]
+    pub removed_options: Vec<usize>,
+    #[prop_or_default]
+    pub show_hint: bool,
+    // Wrong options already tried in practice mode, greyed out while the player tries again
+    #[prop_or_default]
+    pub tried_options: Vec<usize>,
+    #[prop_or_default]
+    pub confidence: Option<Confidence>,
+    // Set when the player has to rate their confidence before submitting
+    #[prop_or_default]
+    pub on_confidence: Option<Callback<Confidence>>,
+    // Attached to the question heading, which the app focuses when a new question is shown
+    #[prop_or_default]
+    pub focus_ref: NodeRef,
+    // Read the outcome of the answer out to screen readers; only the question being played sets it,
+    // a page listing many questions would otherwise hold as many live regions
+    #[prop_or_default]
+    pub announce: bool,
 }
 
 #[function_component(QuestionComponent)]
@@ -14,24 +37,124 @@ pub fn question_component(props: &QuestionProps) -> Html {
     let selected_option = props.selected_option;
     let is_submitted = props.is_submitted;
     let correct_answer = props.question.correct_answer_index;
+    let heading_id = format!("question-{}-heading", props.question.id);
+    
+    // Options the player can still pick, in display order
+    let available = (0..props.question.options.len())
+        .filter(|i| !props.removed_options.contains(i) && !props.tried_options.contains(i))
+        .collect::<Vec<_>>();
+    // Only one radio of the group is reachable with Tab: the checked one, or else the first
+    let tab_stop = selected_option
+        .filter(|i| available.contains(i))
+        .or_else(|| available.first().copied());
+    
+    // Arrow keys move the selection within the group, like native radio buttons
+    let on_group_keydown = {
+        let on_select = props.on_select.clone();
+        let available = available.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if is_submitted || available.is_empty() {
+                return;
+            }
+            let step: isize = match event.key().as_str() {
+                "ArrowDown" | "ArrowRight" => 1,
+                "ArrowUp" | "ArrowLeft" => -1,
+                _ => return,
+            };
+            event.prevent_default();
+            let position = selected_option
+                .and_then(|selected| available.iter().position(|&i| i == selected))
+                .map(|p| (p as isize + step).rem_euclid(available.len() as isize) as usize)
+                .unwrap_or(0);
+            let next = available[position];
+            on_select.emit(next);
+            
+            let option = event
+                .current_target()
+                .and_then(|group| group.dyn_into::<Element>().ok())
+                .and_then(|group| group.query_selector(&format!("[data-option=\"{}\"]", next)).ok().flatten())
+                .and_then(|option| option.dyn_into::<HtmlElement>().ok());
+            if let Some(option) = option {
+                let _ = option.focus();
+            }
+        })
+    };
+    
+    // Read out by screen readers whenever it changes
+    let announcement = if is_submitted {
+        if selected_option == Some(correct_answer) {
+            "Correct! Bonne réponse.".to_string()
+        } else {
+            format!("Incorrect. La bonne réponse est : {}.", props.question.options[correct_answer])
+        }
+    } else if !props.tried_options.is_empty() {
+        "Ce n'est pas la bonne réponse, essayez encore.".to_string()
+    } else {
+        String::new()
+    };
     
     html! {
         <div class="question-container">
-            <h2 class="question-text">{ &props.question.text }</h2>
-            <div class="options-container">
+            <h2 class="question-text" id={heading_id.clone()} ref={props.focus_ref.clone()} tabindex="-1">
+                { &props.question.text }
+            </h2>
+            {
+                match &props.question.media {
+                    Some(media) => {
+                        let alt = media.alt.clone().unwrap_or_default();
+                        match media.kind {
+                            MediaKind::Image => html! {
+                                <img class="question-media" src={media.url.clone()} alt={alt} />
+                            },
+                            MediaKind::Audio => html! {
+                                <audio class="question-media" src={media.url.clone()} controls=true aria-label={alt} />
+                            },
+                            MediaKind::Video => html! {
+                                <video class="question-media" src={media.url.clone()} controls=true aria-label={alt} />
+                            },
+                        }
+                    },
+                    None => html! {},
+                }
+            }
+            {
+                match &props.question.hint {
+                    Some(hint) if props.show_hint => html! {
+                        <p class="hint">{ format!("💡 {}", hint) }</p>
+                    },
+                    _ => html! {},
+                }
+            }
+            <div
+                class="options-container"
+                role="radiogroup"
+                aria-labelledby={heading_id}
+                aria-readonly={is_submitted.to_string()}
+                onkeydown={on_group_keydown}
+            >
                 {
                     props.question.options.iter().enumerate().map(|(index, option)| {
                         let is_selected = selected_option == Some(index);
                         let is_correct = index == correct_answer;
+                        let is_removed = props.removed_options.contains(&index);
+                        let is_tried = props.tried_options.contains(&index);
+                        
+                        if is_removed && !is_submitted {
+                            return html! {};
+                        }
                         
                         let class = if is_submitted {
                             if is_correct {
                                 "option-button correct"
-                            } else if is_selected && !is_correct {
+                            } else if (is_selected && !is_correct) || is_tried {
                                 "option-button incorrect"
+                            } else if is_removed {
+                                "option-button removed"
                             } else {
                                 "option-button"
                             }
+                        } else if is_tried {
+                            "option-button tried"
                         } else if is_selected {
                             "option-button selected"
                         } else {
@@ -51,14 +174,29 @@ pub fn question_component(props: &QuestionProps) -> Html {
                             <button 
                                 class={class}
                                 onclick={on_click}
+                                disabled={is_tried && !is_submitted}
+                                role="radio"
+                                aria-checked={is_selected.to_string()}
+                                tabindex={if tab_stop == Some(index) { "0" } else { "-1" }}
+                                data-option={index.to_string()}
                             >
-                                <span class="option-index">{ format!("{}", ('A' as u8 + index as u8) as char) }</span>
+                                <span class="option-index" aria-hidden="true">{ format!("{}", (b'A' + index as u8) as char) }</span>
                                 <span class="option-text">{ option }</span>
                                 {
                                     if is_submitted && is_correct {
-                                        html! { <span class="checkmark">{ "✓" }</span> }
-                                    } else if is_submitted && is_selected && !is_correct {
-                                        html! { <span class="cross">{ "✗" }</span> }
+                                        html! {
+                                            <>
+                                                <span class="checkmark" aria-hidden="true">{ "✓" }</span>
+                                                <span class="sr-only">{ " (bonne réponse)" }</span>
+                                            </>
+                                        }
+                                    } else if is_submitted && ((is_selected && !is_correct) || is_tried) {
+                                        html! {
+                                            <>
+                                                <span class="cross" aria-hidden="true">{ "✗" }</span>
+                                                <span class="sr-only">{ " (réponse incorrecte)" }</span>
+                                            </>
+                                        }
                                     } else {
                                         html! {}
                                     }
@@ -69,6 +207,47 @@ pub fn question_component(props: &QuestionProps) -> Html {
                 }
             </div>
             
+            {
+                match &props.on_confidence {
+                    Some(on_confidence) if !is_submitted => html! {
+                        <div class="confidence-picker" role="radiogroup" aria-labelledby="confidence-label">
+                            <span id="confidence-label">{ "Votre confiance :" }</span>
+                            {
+                                Confidence::ALL.iter().map(|&level| {
+                                    let class = classes!(
+                                        "confidence-btn",
+                                        (props.confidence == Some(level)).then_some("selected")
+                                    );
+                                    html! {
+                                        <button
+                                            class={class}
+                                            onclick={on_confidence.reform(move |_| level)}
+                                            role="radio"
+                                            aria-checked={(props.confidence == Some(level)).to_string()}
+                                        >
+                                            { level.label() }
+                                        </button>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </div>
+                    },
+                    _ => html! {},
+                }
+            }
+            
+            {
+                if !is_submitted && !props.tried_options.is_empty() {
+                    html! {
+                        <div class="feedback incorrect">
+                            { "Ce n'est pas la bonne réponse, essayez encore." }
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            
             {
                 if is_submitted {
                     let feedback_class = if selected_option == Some(correct_answer) {
@@ -77,21 +256,43 @@ pub fn question_component(props: &QuestionProps) -> Html {
                         "feedback incorrect"
                     };
                     
-                    let feedback_text = if selected_option == Some(correct_answer) {
-                        "Correct! Bonne réponse."
+                    let feedback_text = if selected_option != Some(correct_answer) {
+                        format!("Incorrect. La bonne réponse est : {}.", props.question.options[correct_answer])
+                    } else if props.tried_options.is_empty() {
+                        "Correct! Bonne réponse.".to_string()
                     } else {
-                        "Incorrect. La bonne réponse est indiquée en vert."
+                        format!("Correct! Trouvé en {} essais.", props.tried_options.len() + 1)
                     };
                     
                     html! {
-                        <div class={feedback_class}>
-                            { feedback_text }
-                        </div>
+                        <>
+                            <div class={feedback_class}>
+                                { feedback_text }
+                                {
+                                    if let Some(confidence) = props.confidence {
+                                        html! { <span class="feedback-confidence">{ format!(" (confiance : {})", confidence.label().to_lowercase()) }</span> }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                            </div>
+                            {
+                                if let Some(explanation) = &props.question.explanation {
+                                    html! { <p class="explanation">{ explanation }</p> }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                        </>
                     }
                 } else {
                     html! {}
                 }
             }
+            
+            if props.announce {
+                <div class="sr-only" role="status" aria-live="polite">{ announcement }</div>
+            }
         </div>
     }
 }
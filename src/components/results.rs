@@ -0,0 +1,151 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::components::{CalibrationSummary, CertificatePanel, ChallengeComparison, ChallengePanel, SharePanel};
+use crate::packs::Pack;
+use crate::routes::Route;
+use crate::session::{QuizMode, QuizSession};
+use crate::share::{Challenge, SharedResult};
+
+#[derive(Properties, PartialEq)]
+pub struct ResultsProps {
+    pub session: QuizSession,
+    pub survival_best: usize,
+    pub new_record: bool,
+    // Link for a friend to replay this exact quiz
+    pub challenge: Challenge,
+    // Title of the pack played, printed on the certificate
+    pub quiz_title: AttrValue,
+    pub on_retry: Callback<()>,
+    // Focused by the app when the results are shown
+    #[prop_or_default]
+    pub retry_ref: NodeRef,
+}
+
+#[function_component(ResultsScreen)]
+pub fn results_screen(props: &ResultsProps) -> Html {
+    let session = &props.session;
+    let score = session.score();
+    let shared = SharedResult::from_session(session);
+    // Nobody else can open the file an imported quiz was read from
+    let shareable = Pack::is_shareable(&session.pack);
+
+    let actions = html! {
+        <>
+            <ChallengeComparison session={session.clone()} />
+            // The seed of a replayed challenge does not draw its questions again
+            if shareable && session.opponent.is_none() {
+                <SharePanel shared={shared} />
+            }
+            if shareable {
+                <ChallengePanel challenge={props.challenge.clone()} score={score} />
+            }
+            <button ref={props.retry_ref.clone()} class="retry-btn" onclick={props.on_retry.reform(|_| ())}>
+                { "Recommencer" }
+            </button>
+            <div class="result-links">
+                <Link<Route> to={Route::Review} classes="skip-btn">{ "Voir le détail" }</Link<Route>>
+                <Link<Route> to={Route::Home} classes="skip-btn">{ "Changer de mode" }</Link<Route>>
+            </div>
+        </>
+    };
+
+    if session.mode == QuizMode::Survival {
+        return html! {
+            <div class="result-container">
+                <h2>{ "Partie Terminée!" }</h2>
+                <div class="result-emoji">{ if props.new_record { "🏆" } else { "💔" } }</div>
+                <div class="score">{ score } <span>{ if score > 1 { " bonnes réponses" } else { " bonne réponse" } }</span></div>
+                {
+                    if props.new_record {
+                        html! { <p>{ "Nouveau record personnel !" }</p> }
+                    } else {
+                        html! { <p>{ format!("Votre record : {}", props.survival_best) }</p> }
+                    }
+                }
+                { actions }
+            </div>
+        };
+    }
+
+    let total = session.len();
+    let lifelines_used = session.progress.iter().map(|p| p.lifelines_used()).sum::<usize>();
+    let percentage = session.percentage();
+
+    // Choose emoji and message from the quiz's score bands
+    let (emoji, message) = session
+        .grading
+        .band(percentage)
+        .map(|band| (band.emoji.as_str(), band.message.as_str()))
+        .unwrap_or_default();
+
+    html! {
+        <div class="result-container">
+            <h2>{ "Quiz Terminé!" }</h2>
+            <div class="result-emoji">{ emoji }</div>
+            <div class="score">{ score } <span>{ format!("/{}", total) }</span></div>
+            {
+                if session.mode == QuizMode::Practice {
+                    let attempts = session.progress.iter().map(|p| p.attempts()).sum::<usize>();
+                    html! {
+                        <>
+                            <p>{ format!("Vous avez trouvé {}% des réponses du premier coup", percentage) }</p>
+                            <p class="attempts-summary">{ format!("{} tentatives au total", attempts) }</p>
+                        </>
+                    }
+                } else {
+                    html! { <p>{ format!("Vous avez obtenu {}% de bonnes réponses", percentage) }</p> }
+                }
+            }
+            {
+                if lifelines_used > 0 {
+                    html! {
+                        <p class="lifeline-summary">
+                            { format!("Jokers utilisés : {}", lifelines_used) }
+                            if session.penalizes_lifelines() {
+                                { format!(" — score après pénalités : {} / {}", session.points(), total) }
+                            }
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <p class={if session.passed() { "pass-status passed" } else { "pass-status failed" }}>
+                {
+                    if session.passed() {
+                        format!("Réussi ! (seuil de réussite : {} %)", session.pass_mark())
+                    } else {
+                        format!("Non réussi — il fallait {} % pour réussir", session.pass_mark())
+                    }
+                }
+            </p>
+            <p style="margin-top: 1rem; color: var(--neutral-color);">{ message }</p>
+            
+            if session.uses_confidence() {
+                <p class="confidence-score">
+                    { format!("Score pondéré par la confiance : {} / {}", session.confidence_score(), session.max_confidence_score()) }
+                </p>
+                <CalibrationSummary rows={session.calibration()} />
+            }
+            
+            if session.passed() {
+                <CertificatePanel session={session.clone()} quiz_title={props.quiz_title.clone()} />
+            }
+            
+            { actions }
+            
+            { if session.grading.celebrates(percentage) {
+                // Create confetti effect for high scores
+                (0..20).map(|i| {
+                    let left = format!("{}%", i * 5);
+                    let delay = format!("{}s", (i as f32 * 0.1) % 5.0);
+                    html! {
+                        <div class="confetti" style={format!("left: {}; animation-delay: {}", left, delay)}></div>
+                    }
+                }).collect::<Html>()
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
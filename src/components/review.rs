@@ -0,0 +1,104 @@
+use yew::prelude::*;
+use crate::components::QuestionComponent;
+use crate::session::{QuizMode, QuizSession};
+
+#[derive(Properties, PartialEq)]
+pub struct ReviewProps {
+    pub session: QuizSession,
+}
+
+// Format a duration in milliseconds as "42 s" or "1 min 05 s"
+fn format_duration(ms: f64) -> String {
+    let seconds = (ms / 1000.0).round() as u64;
+    if seconds < 60 {
+        format!("{} s", seconds)
+    } else {
+        format!("{} min {:02} s", seconds / 60, seconds % 60)
+    }
+}
+
+#[function_component(ResultsReview)]
+pub fn results_review(props: &ReviewProps) -> Html {
+    let mistakes_only = use_state(|| false);
+    let session = &props.session;
+
+    let on_toggle_filter = {
+        let mistakes_only = mistakes_only.clone();
+        Callback::from(move |_| mistakes_only.set(!*mistakes_only))
+    };
+
+    let shown = session
+        .played()
+        .into_iter()
+        .filter(|&i| !*mistakes_only || !session.is_correct(i))
+        .collect::<Vec<_>>();
+
+    html! {
+        <div class="review-container">
+            <div class="review-header">
+                <h3>{ "Détail des réponses" }</h3>
+                <label class="review-filter">
+                    <input type="checkbox" checked={*mistakes_only} onchange={on_toggle_filter} />
+                    { "Erreurs uniquement" }
+                </label>
+            </div>
+            {
+                if shown.is_empty() {
+                    html! { <p class="review-empty">{ "Aucune erreur, bravo !" }</p> }
+                } else {
+                    shown.into_iter().map(|i| {
+                        let question = &session.questions[i];
+                        let progress = &session.progress[i];
+                        let answer = if progress.submitted { progress.answer } else { None };
+                        let user_answer = answer
+                            .and_then(|a| question.options.get(a))
+                            .map(|a| a.as_str())
+                            .unwrap_or("Sans réponse");
+                        let correct_answer = &question.options[question.correct_answer_index];
+                        let item_class = if session.is_correct(i) {
+                            "review-item correct"
+                        } else {
+                            "review-item incorrect"
+                        };
+
+                        html! {
+                            <div class={item_class}>
+                                <div class="review-meta">
+                                    <span>{ format!("Question {}", i + 1) }</span>
+                                    <span>
+                                        if !progress.removed_options.is_empty() {
+                                            <span class="lifeline-badge">{ "50/50" }</span>
+                                        }
+                                        if progress.hint_shown {
+                                            <span class="lifeline-badge">{ "Indice" }</span>
+                                        }
+                                        if session.mode == QuizMode::Practice {
+                                            <span class="attempts">{ format!("{} tentative(s) · ", progress.attempts()) }</span>
+                                        }
+                                        { format!("⏱ {}", format_duration(progress.time_ms)) }
+                                    </span>
+                                </div>
+                                <QuestionComponent
+                                    question={question.clone()}
+                                    selected_option={answer}
+                                    is_submitted={true}
+                                    on_select={Callback::noop()}
+                                    removed_options={progress.removed_options.clone()}
+                                    show_hint={progress.hint_shown}
+                                    confidence={progress.confidence}
+                                    tried_options={progress.wrong_attempts.clone()}
+                                />
+                                <p class="review-answer">
+                                    <strong>{ "Votre réponse : " }</strong>{ user_answer }
+                                </p>
+                                <p class="review-answer">
+                                    <strong>{ "Bonne réponse : " }</strong>{ correct_answer }
+                                </p>
+                            </div>
+                        }
+                    }).collect::<Html>()
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,85 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use crate::session::{QuizOptions, LIFELINE_PENALTY};
+
+#[derive(Properties, PartialEq)]
+pub struct SettingsProps {
+    pub options: QuizOptions,
+    pub on_change: Callback<QuizOptions>,
+}
+
+#[function_component(SettingsPage)]
+pub fn settings_page(props: &SettingsProps) -> Html {
+    let on_penalty_change = {
+        let options = props.options.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut updated = options.clone();
+            updated.penalize_lifelines = input.checked();
+            on_change.emit(updated);
+        })
+    };
+
+    let on_confidence_change = {
+        let options = props.options.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut updated = options.clone();
+            updated.confidence_marking = input.checked();
+            on_change.emit(updated);
+        })
+    };
+
+    let on_pass_mark_change = {
+        let options = props.options.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            // Invalid input is ignored, the field then shows the current pass mark again
+            if let Ok(pass_mark) = input.value().parse::<usize>() {
+                let mut updated = options.clone();
+                updated.pass_mark = pass_mark.min(100);
+                on_change.emit(updated);
+            }
+        })
+    };
+
+    html! {
+        <div class="settings-container">
+            <h2>{ "Paramètres" }</h2>
+            <p class="settings-note">{ "Ces options s'appliquent aux quiz classiques et sont enregistrées sur cet appareil." }</p>
+            <div class="quiz-options">
+                <label>
+                    <input
+                        type="checkbox"
+                        checked={props.options.penalize_lifelines}
+                        onchange={on_penalty_change}
+                    />
+                    { format!("Pénaliser les jokers (-{} point par joker utilisé)", LIFELINE_PENALTY) }
+                </label>
+                <label>
+                    <input
+                        type="checkbox"
+                        checked={props.options.confidence_marking}
+                        onchange={on_confidence_change}
+                    />
+                    { "Noter selon ma confiance dans chaque réponse" }
+                </label>
+                <label>
+                    { "Score minimum pour réussir et obtenir le certificat (%)" }
+                    <input
+                        class="pass-mark-input"
+                        type="number"
+                        min="0"
+                        max="100"
+                        step="5"
+                        value={props.options.pass_mark.to_string()}
+                        onchange={on_pass_mark_change}
+                    />
+                </label>
+            </div>
+        </div>
+    }
+}
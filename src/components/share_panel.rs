@@ -0,0 +1,55 @@
+use yew::prelude::*;
+use crate::components::QrCodeSvg;
+use crate::share::{copy_to_clipboard, SharedResult};
+
+#[derive(Properties, PartialEq)]
+pub struct SharePanelProps {
+    pub shared: SharedResult,
+}
+
+#[function_component(SharePanel)]
+pub fn share_panel(props: &SharePanelProps) -> Html {
+    let show_qr = use_state(|| false);
+    let copied = use_state(|| None::<&'static str>);
+    let url = props.shared.url();
+
+    let on_copy_link = {
+        let copied = copied.clone();
+        let url = url.clone();
+        Callback::from(move |_| {
+            copy_to_clipboard(url.clone());
+            copied.set(Some("Lien copié !"));
+        })
+    };
+
+    let on_copy_text = {
+        let copied = copied.clone();
+        let text = props.shared.text();
+        Callback::from(move |_| {
+            copy_to_clipboard(text.clone());
+            copied.set(Some("Résultat copié !"));
+        })
+    };
+
+    let on_toggle_qr = {
+        let show_qr = show_qr.clone();
+        Callback::from(move |_| show_qr.set(!*show_qr))
+    };
+
+    html! {
+        <div class="share-panel">
+            <h3>{ "Partager mon résultat" }</h3>
+            <pre class="share-grid" aria-label="Grille des bonnes et mauvaises réponses">{ props.shared.emoji_grid() }</pre>
+            <input class="share-link" type="text" readonly=true value={url.clone()} aria-label="Lien de partage" />
+            <div class="share-actions">
+                <button class="skip-btn" onclick={on_copy_link}>{ "Copier le lien" }</button>
+                <button class="skip-btn" onclick={on_copy_text}>{ "Copier le texte" }</button>
+                <button class="skip-btn" onclick={on_toggle_qr} aria-expanded={show_qr.to_string()}>{ "QR code" }</button>
+            </div>
+            if *show_qr {
+                <QrCodeSvg data={url} label="QR code du lien de partage" />
+            }
+            <p class="share-status" role="status" aria-live="polite">{ (*copied).unwrap_or_default() }</p>
+        </div>
+    }
+}
@@ -0,0 +1,52 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::components::PackMismatch;
+use crate::packs::{linked_pack_id, Pack};
+use crate::routes::Route;
+use crate::share::SharedResult;
+
+#[derive(Properties, PartialEq)]
+pub struct SharedResultProps {
+    // Pack of the loaded questions, the quiz is only drawn again from the bank it was drawn from
+    pub loaded_pack: String,
+    pub packs: Vec<Pack>,
+    pub on_load_pack: Callback<String>,
+    pub on_play: Callback<SharedResult>,
+}
+
+// Landing page of a share link: the sharer's result and a way to play the same quiz
+#[function_component(SharedResultPage)]
+pub fn shared_result_page(props: &SharedResultProps) -> Html {
+    let shared = use_location().and_then(|location| location.query::<SharedResult>().ok());
+
+    let Some(shared) = shared else {
+        return html! {
+            <div class="error-container">
+                <p>{ "Ce lien de partage est incomplet ou invalide." }</p>
+                <Link<Route> to={Route::Home}>{ "Choisir un mode de jeu" }</Link<Route>>
+            </div>
+        };
+    };
+
+    let pack = linked_pack_id(&shared.pack, &props.packs);
+    if pack != props.loaded_pack {
+        return html! { <PackMismatch pack={pack} packs={props.packs.clone()} on_load_pack={props.on_load_pack.clone()} /> };
+    }
+
+    let on_play = {
+        let shared = shared.clone();
+        props.on_play.reform(move |_| shared.clone())
+    };
+
+    html! {
+        <div class="result-container">
+            <h2>{ "Un ami vous défie !" }</h2>
+            <p>{ "Son résultat :" }</p>
+            <div class="score">{ shared.headline() }</div>
+            <pre class="share-grid">{ shared.emoji_grid() }</pre>
+            <button class="retry-btn" onclick={on_play}>
+                { "Jouer le même quiz" }
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,42 @@
+use yew::prelude::*;
+use crate::shortcuts::SHORTCUT_HELP;
+
+#[derive(Properties, PartialEq)]
+pub struct ShortcutHelpProps {
+    pub on_close: Callback<()>,
+}
+
+#[function_component(ShortcutHelp)]
+pub fn shortcut_help(props: &ShortcutHelpProps) -> Html {
+    let close_ref = use_node_ref();
+
+    // Put the focus in the overlay so it can be closed right away
+    {
+        let close_ref = close_ref.clone();
+        use_effect_with((), move |_| {
+            crate::shortcuts::focus(&close_ref);
+            || ()
+        });
+    }
+
+    html! {
+        <div class="shortcut-overlay" onclick={props.on_close.reform(|_| ())}>
+            <div class="shortcut-dialog" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <h3>{ "Raccourcis clavier" }</h3>
+                <dl>
+                    {
+                        SHORTCUT_HELP.iter().map(|(keys, action)| html! {
+                            <>
+                                <dt><kbd>{ *keys }</kbd></dt>
+                                <dd>{ *action }</dd>
+                            </>
+                        }).collect::<Html>()
+                    }
+                </dl>
+                <button ref={close_ref} class="submit-btn" onclick={props.on_close.reform(|_| ())}>
+                    { "Fermer" }
+                </button>
+            </div>
+        </div>
+    }
+}
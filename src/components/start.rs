@@ -0,0 +1,90 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::packs::Pack;
+use crate::routes::Route;
+use crate::session::{QuizMode, SURVIVAL_LIVES};
+
+#[derive(Properties, PartialEq)]
+pub struct StartProps {
+    pub quiz_size: usize,
+    pub survival_best: usize,
+    pub on_start: Callback<QuizMode>,
+    pub packs: Vec<Pack>,
+    pub selected_pack: String,
+    pub on_select_pack: Callback<String>,
+}
+
+#[function_component(StartScreen)]
+pub fn start_screen(props: &StartProps) -> Html {
+    let on_classic = props.on_start.reform(|_| QuizMode::Classic);
+    let on_practice = props.on_start.reform(|_| QuizMode::Practice);
+    let on_survival = props.on_start.reform(|_| QuizMode::Survival);
+
+    let on_pack_change = props.on_select_pack.reform(|e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        select.value()
+    });
+    let selected_pack = props.packs.iter().find(|pack| pack.id == props.selected_pack);
+
+    html! {
+        <div class="start-container">
+            if props.packs.len() > 1 {
+                <div class="pack-picker">
+                    <label for="pack-select">{ "Thème" }</label>
+                    <select id="pack-select" onchange={on_pack_change}>
+                        {
+                            props.packs.iter().map(|pack| {
+                                let label = match pack.question_count {
+                                    Some(count) => format!("{} ({} questions, {})", pack.title, count, pack.language),
+                                    None => format!("{} ({})", pack.title, pack.language),
+                                };
+                                html! {
+                                    <option value={pack.id.clone()} selected={pack.id == props.selected_pack}>
+                                        { label }
+                                    </option>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </select>
+                    if let Some(pack) = selected_pack {
+                        <p class="pack-description">{ &pack.description }</p>
+                    }
+                </div>
+            }
+            <h2>{ "Choisissez un mode de jeu" }</h2>
+            <div class="mode-options">
+                <button class="mode-card" onclick={on_classic}>
+                    <span class="mode-title">{ "Quiz classique" }</span>
+                    <span class="mode-description">
+                        { format!("{} questions, à passer ou marquer pour y revenir plus tard", props.quiz_size) }
+                    </span>
+                </button>
+                <button class="mode-card" onclick={on_practice}>
+                    <span class="mode-title">{ "Entraînement" }</span>
+                    <span class="mode-description">
+                        { format!("{} questions, réessayez jusqu'à trouver la bonne réponse", props.quiz_size) }
+                    </span>
+                </button>
+                <button class="mode-card" onclick={on_survival}>
+                    <span class="mode-title">{ "Mode survie" }</span>
+                    <span class="mode-description">
+                        { format!("{} vies, et des questions jusqu'à la dernière erreur", SURVIVAL_LIVES) }
+                    </span>
+                    {
+                        if props.survival_best > 0 {
+                            html! { <span class="mode-best">{ format!("Record : {}", props.survival_best) }</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </button>
+            </div>
+            <p class="quiz-options">
+                { "Pénalités des jokers et notation selon la confiance : voir les " }
+                <Link<Route> to={Route::Settings}>{ "paramètres" }</Link<Route>>
+                { "." }
+            </p>
+        </div>
+    }
+}
@@ -0,0 +1,83 @@
+use yew::prelude::*;
+use crate::history::load_history;
+use crate::session::QuizMode;
+use crate::storage::{self, SURVIVAL_BEST_KEY};
+
+fn mode_label(mode: QuizMode) -> &'static str {
+    match mode {
+        QuizMode::Classic => "Classique",
+        QuizMode::Practice => "Entraînement",
+        QuizMode::Survival => "Survie",
+    }
+}
+
+#[function_component(StatsPage)]
+pub fn stats_page() -> Html {
+    let history = use_memo((), |_| load_history());
+    let survival_best = storage::load::<usize>(SURVIVAL_BEST_KEY).unwrap_or(0);
+
+    if history.is_empty() {
+        return html! {
+            <div class="stats-container">
+                <h2>{ "Statistiques" }</h2>
+                <p class="review-empty">{ "Terminez un premier quiz pour voir vos statistiques." }</p>
+            </div>
+        };
+    }
+
+    let answered = history.iter().map(|r| r.answered).sum::<usize>();
+    let correct = history.iter().map(|r| r.score).sum::<usize>();
+    let time_ms = history.iter().map(|r| r.time_ms).sum::<f64>();
+    let success_rate = (correct * 100).checked_div(answered).unwrap_or(0);
+    let seconds_per_question = if answered > 0 { time_ms / answered as f64 / 1000.0 } else { 0.0 };
+
+    html! {
+        <div class="stats-container">
+            <h2>{ "Statistiques" }</h2>
+            <div class="stats-grid">
+                <div class="stat">
+                    <span class="stat-value">{ history.len() }</span>
+                    <span class="stat-label">{ "parties jouées" }</span>
+                </div>
+                <div class="stat">
+                    <span class="stat-value">{ format!("{}%", success_rate) }</span>
+                    <span class="stat-label">{ "de bonnes réponses" }</span>
+                </div>
+                <div class="stat">
+                    <span class="stat-value">{ format!("{:.0} s", seconds_per_question) }</span>
+                    <span class="stat-label">{ "par question en moyenne" }</span>
+                </div>
+                <div class="stat">
+                    <span class="stat-value">{ survival_best }</span>
+                    <span class="stat-label">{ "record en mode survie" }</span>
+                </div>
+            </div>
+            
+            <h3>{ "Dernières parties" }</h3>
+            <table class="stats-history">
+                <thead>
+                    <tr>
+                        <th>{ "Date" }</th>
+                        <th>{ "Mode" }</th>
+                        <th>{ "Score" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        history.iter().rev().take(10).map(|record| {
+                            let date = js_sys::Date::new(&record.finished_at.into());
+                            let date = String::from(date.to_locale_date_string("fr-FR", &wasm_bindgen::JsValue::UNDEFINED));
+                            html! {
+                                <tr>
+                                    <td>{ date }</td>
+                                    <td>{ mode_label(record.mode) }</td>
+                                    <td>{ format!("{} / {}", record.score, record.answered) }</td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,262 @@
+// Rules for the rows of a CSV bank. This file is also compiled into build.rs, which checks the bank
+// shipped with the app with exactly the rules the app loads banks with, so it only uses `csv` and std.
+use std::collections::HashMap;
+use std::fmt;
+
+// Columns every CSV bank must have, `id`, `explanation` and `hint` being optional
+const COLUMNS: [&str; 6] = ["question", "option1", "option2", "option3", "option4", "correct_answer_index"];
+const OPTIONS: [&str; 4] = ["option1", "option2", "option3", "option4"];
+
+// A row that passed every check
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvRow {
+    pub id: usize,
+    pub question: String,
+    pub options: Vec<String>,
+    pub correct_answer_index: usize,
+    pub explanation: Option<String>,
+    pub hint: Option<String>,
+}
+
+// Why a row of a CSV bank was left out
+#[derive(Clone, Debug, PartialEq)]
+pub struct BankIssue {
+    // Line in the file, the header being line 1
+    pub line: usize,
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl BankIssue {
+    pub fn new(line: usize, column: Option<&str>, message: String) -> Self {
+        Self {
+            line,
+            column: column.map(str::to_string),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for BankIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "ligne {}, colonne {} : {}", self.line, column, self.message),
+            None => write!(f, "ligne {} : {}", self.line, self.message),
+        }
+    }
+}
+
+// Read every row of a CSV bank, keeping the valid ones and reporting every problem of the others
+pub fn parse_rows(csv_data: &str) -> (Vec<CsvRow>, Vec<BankIssue>) {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(csv_data.as_bytes());
+    let mut rows = Vec::new();
+    let mut issues = Vec::new();
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            issues.push(BankIssue::new(1, None, format!("en-tête illisible ({})", e)));
+            return (rows, issues);
+        }
+    };
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let missing = COLUMNS.iter().filter(|name| column(name).is_none()).copied().collect::<Vec<_>>();
+    if !missing.is_empty() {
+        issues.push(BankIssue::new(1, None, format!("colonne(s) manquante(s) : {}", missing.join(", "))));
+        return (rows, issues);
+    }
+
+    // Line of each ID already taken by a valid row, the first row keeps it
+    let mut seen = HashMap::<usize, usize>::new();
+    for result in reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map_or(0, |position| position.line() as usize);
+                issues.push(BankIssue::new(line, None, format!("ligne illisible ({})", e)));
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |position| position.line() as usize);
+        if record.len() != headers.len() {
+            issues.push(BankIssue::new(line, None, format!("{} champs au lieu de {}", record.len(), headers.len())));
+            continue;
+        }
+        let field = |name: &str| column(name).and_then(|i| record.get(i)).unwrap_or_default();
+        let mut row_issues = Vec::new();
+
+        // Banks without an ID column number their questions after their line
+        let id = match column("id") {
+            Some(_) => match field("id").parse::<usize>() {
+                Ok(id) => Some(id),
+                Err(_) => {
+                    row_issues.push(BankIssue::new(line, Some("id"), format!("identifiant non numérique « {} »", field("id"))));
+                    None
+                }
+            },
+            None => Some(line - 1),
+        };
+        if let Some(first) = id.and_then(|id| seen.get(&id)) {
+            row_issues.push(BankIssue::new(line, Some("id"), format!("identifiant déjà utilisé ligne {}", first)));
+        }
+
+        if field("question").is_empty() {
+            row_issues.push(BankIssue::new(line, Some("question"), "question vide".to_string()));
+        }
+        for name in OPTIONS {
+            if field(name).is_empty() {
+                row_issues.push(BankIssue::new(line, Some(name), "option vide".to_string()));
+            }
+        }
+
+        let index = field("correct_answer_index");
+        let correct_answer_index = match index.parse::<usize>() {
+            Ok(i) if i < OPTIONS.len() => Some(i),
+            Ok(i) => {
+                row_issues.push(BankIssue::new(
+                    line,
+                    Some("correct_answer_index"),
+                    format!("index {} hors limites, il doit être entre 0 et {}", i, OPTIONS.len() - 1),
+                ));
+                None
+            }
+            Err(_) => {
+                row_issues.push(BankIssue::new(line, Some("correct_answer_index"), format!("index non numérique « {} »", index)));
+                None
+            }
+        };
+
+        match (id, correct_answer_index) {
+            (Some(id), Some(correct_answer_index)) if row_issues.is_empty() => {
+                // Optional columns, older banks don't have them
+                let optional = |name: &str| Some(field(name)).filter(|text| !text.is_empty()).map(str::to_string);
+                seen.insert(id, line);
+                rows.push(CsvRow {
+                    id,
+                    question: field("question").to_string(),
+                    options: OPTIONS.iter().map(|name| field(name).to_string()).collect(),
+                    correct_answer_index,
+                    explanation: optional("explanation"),
+                    hint: optional("hint"),
+                });
+            }
+            _ => issues.append(&mut row_issues),
+        }
+    }
+
+    (rows, issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "id,question,option1,option2,option3,option4,correct_answer_index,explanation\n";
+
+    fn parse(rows: &str) -> (Vec<CsvRow>, Vec<String>) {
+        let (rows, issues) = parse_rows(&format!("{}{}", HEADER, rows));
+        (rows, issues.iter().map(|issue| issue.to_string()).collect())
+    }
+
+    #[test]
+    fn valid_rows_are_kept_with_their_optional_columns() {
+        let (rows, issues) = parse("1, Q ? ,a,b,c,d,2,Parce que\n2,R ?,a,b,c,d,0,\n");
+        assert!(issues.is_empty());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].question, "Q ?");
+        assert_eq!(rows[0].correct_answer_index, 2);
+        assert_eq!(rows[0].explanation.as_deref(), Some("Parce que"));
+        assert_eq!(rows[1].explanation, None);
+        assert_eq!(rows[1].hint, None);
+    }
+
+    #[test]
+    fn wrong_field_count() {
+        let (rows, issues) = parse("1,Q ?,a,b,c,d,0,\n2,R ?,a,b\n");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(issues, ["ligne 3 : 4 champs au lieu de 8"]);
+    }
+
+    #[test]
+    fn non_numeric_index() {
+        let (rows, issues) = parse("1,Q ?,a,b,c,d,deux,\n");
+        assert!(rows.is_empty());
+        assert_eq!(issues, ["ligne 2, colonne correct_answer_index : index non numérique « deux »"]);
+    }
+
+    #[test]
+    fn index_out_of_range() {
+        let (rows, issues) = parse("1,Q ?,a,b,c,d,4,\n");
+        assert!(rows.is_empty());
+        assert_eq!(issues, ["ligne 2, colonne correct_answer_index : index 4 hors limites, il doit être entre 0 et 3"]);
+    }
+
+    #[test]
+    fn empty_question_and_options_are_all_reported() {
+        let (rows, issues) = parse("1,,a,,c, ,0,\n");
+        assert!(rows.is_empty());
+        assert_eq!(
+            issues,
+            [
+                "ligne 2, colonne question : question vide",
+                "ligne 2, colonne option2 : option vide",
+                "ligne 2, colonne option4 : option vide",
+            ]
+        );
+    }
+
+    #[test]
+    fn non_numeric_id() {
+        let (rows, issues) = parse("un,Q ?,a,b,c,d,0,\n");
+        assert!(rows.is_empty());
+        assert_eq!(issues, ["ligne 2, colonne id : identifiant non numérique « un »"]);
+    }
+
+    #[test]
+    fn duplicate_id_keeps_the_first_row() {
+        let (rows, issues) = parse("7,Q ?,a,b,c,d,0,\n7,R ?,a,b,c,d,1,\n");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].question, "Q ?");
+        assert_eq!(issues, ["ligne 3, colonne id : identifiant déjà utilisé ligne 2"]);
+    }
+
+    #[test]
+    fn lines_count_multi_line_fields() {
+        let (_, issues) = parse("1,\"Q\nsur deux lignes\",a,b,c,d,0,\n2,R ?,a,b,c,d,9,\n");
+        assert_eq!(issues, ["ligne 4, colonne correct_answer_index : index 9 hors limites, il doit être entre 0 et 3"]);
+    }
+
+    #[test]
+    fn missing_id_column_numbers_rows_by_line() {
+        let (rows, issues) = parse_rows("question,option1,option2,option3,option4,correct_answer_index\nQ ?,a,b,c,d,0\nR ?,a,b,c,d,1\n");
+        assert!(issues.is_empty());
+        assert_eq!(rows.iter().map(|row| row.id).collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn missing_required_columns() {
+        let (rows, issues) = parse_rows("id,question,option1,correct_answer_index\n1,Q ?,a,0\n");
+        assert!(rows.is_empty());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].to_string(), "ligne 1 : colonne(s) manquante(s) : option2, option3, option4");
+    }
+
+    // A comma left unquoted in a question splits it, its end then takes the place of an option
+    #[test]
+    fn shipped_bank_has_whole_questions_and_real_answers() {
+        let (rows, issues) = parse_rows(include_str!("../static/questions.csv"));
+        assert!(issues.is_empty());
+        for row in rows {
+            assert!(row.question.ends_with('?'), "question {} is cut: {}", row.id, row.question);
+            for option in &row.options {
+                assert!(!option.contains('?'), "question {} has a piece of question as option: {}", row.id, option);
+            }
+            let answer = &row.options[row.correct_answer_index];
+            let repeated = row.options.iter().filter(|option| *option == answer).count();
+            assert_eq!(repeated, 1, "question {} repeats its answer {}", row.id, answer);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+// Why a question bank could not be played, `source` being its URL or the name of the imported file
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadError {
+    // Browsers do not tell a network failure from a refused cross-origin request
+    Network { url: String },
+    Http { url: String, status: u16, status_text: String },
+    // The response body could not be read as text
+    Decode { url: String },
+    // Not readable as CSV, JSON or TOML
+    Parse { source: String, message: String },
+    // Readable, but not a single question of it can be played
+    Validation { source: String, message: String },
+    InsufficientQuestions { source: String, found: usize, needed: usize },
+}
+
+impl LoadError {
+    // Heading shown above the message
+    pub fn title(&self) -> &'static str {
+        match self {
+            LoadError::Network { .. } => "Connexion impossible",
+            LoadError::Http { .. } => "Erreur du serveur",
+            LoadError::Decode { .. } => "Réponse illisible",
+            LoadError::Parse { .. } | LoadError::Validation { .. } => "Banque de questions invalide",
+            LoadError::InsufficientQuestions { .. } => "Pas assez de questions",
+        }
+    }
+
+    // Whether trying again later may work; an invalid bank stays invalid until its author fixes it
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LoadError::Network { .. } | LoadError::Decode { .. } => true,
+            LoadError::Http { status, .. } => *status >= 500 || *status == 408 || *status == 429,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Network { url } => write!(
+                f,
+                "Impossible de joindre {}. Vérifiez l'adresse, votre connexion, et que le serveur autorise les requêtes d'autres sites (CORS).",
+                url
+            ),
+            LoadError::Http { url, status: 404, .. } => write!(f, "{} est introuvable (erreur 404).", url),
+            LoadError::Http { url, status, status_text } => {
+                write!(f, "Le serveur a répondu {} {} pour {}.", status, status_text, url)
+            }
+            LoadError::Decode { url } => write!(f, "Le contenu de {} n'a pas pu être lu comme du texte.", url),
+            LoadError::Parse { source, message } => {
+                write!(f, "{} n'est pas une banque de questions valide. {}", source, message)
+            }
+            LoadError::Validation { source, message } => {
+                write!(f, "Aucune question de {} ne peut être jouée. {}", source, message)
+            }
+            LoadError::InsufficientQuestions { source, found: 0, .. } => {
+                write!(f, "{} ne contient aucune question.", source)
+            }
+            LoadError::InsufficientQuestions { source, found, needed } => write!(
+                f,
+                "{} ne contient que {} question(s) jouable(s), il en faut au moins {}.",
+                source, found, needed
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http(status: u16) -> LoadError {
+        LoadError::Http {
+            url: "banque.csv".to_string(),
+            status,
+            status_text: String::new(),
+        }
+    }
+
+    #[test]
+    fn network_and_server_failures_are_retryable() {
+        assert!(LoadError::Network { url: "banque.csv".to_string() }.is_retryable());
+        assert!(LoadError::Decode { url: "banque.csv".to_string() }.is_retryable());
+        for status in [408, 429, 500, 502, 503] {
+            assert!(http(status).is_retryable(), "{}", status);
+        }
+    }
+
+    #[test]
+    fn invalid_banks_and_client_errors_are_not_retryable() {
+        for status in [400, 403, 404] {
+            assert!(!http(status).is_retryable(), "{}", status);
+        }
+        let source = "banque.csv".to_string();
+        assert!(!LoadError::Parse { source: source.clone(), message: String::new() }.is_retryable());
+        assert!(!LoadError::Validation { source: source.clone(), message: String::new() }.is_retryable());
+        assert!(!LoadError::InsufficientQuestions { source, found: 3, needed: 10 }.is_retryable());
+    }
+}
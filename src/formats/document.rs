@@ -0,0 +1,282 @@
+// Question banks written as documents (JSON or TOML) rather than CSV rows. Both formats share the
+// structure described by static/question-bank.schema.json.
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::formats::QuestionBank;
+use crate::grading::Grading;
+use crate::models::{Media, Question};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BankDocument {
+    // Pointer to the schema, for editors; not used by the app
+    #[serde(default, rename = "$schema")]
+    _schema: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    grading: Option<Grading>,
+    questions: Vec<DocumentQuestion>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AnswerType {
+    #[default]
+    Choice,
+    TrueFalse,
+}
+
+// An option is its text, or an object that can also mark it as the right one
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DocumentOption {
+    Text(String),
+    Detailed {
+        text: String,
+        #[serde(default)]
+        correct: bool,
+    },
+}
+
+impl DocumentOption {
+    fn text(&self) -> &str {
+        match self {
+            DocumentOption::Text(text) | DocumentOption::Detailed { text, .. } => text,
+        }
+    }
+
+    fn is_marked_correct(&self) -> bool {
+        matches!(self, DocumentOption::Detailed { correct: true, .. })
+    }
+}
+
+// The right answer: its position, the text of the option, or true/false
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DocumentAnswer {
+    Index(usize),
+    Boolean(bool),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DocumentQuestion {
+    id: usize,
+    #[serde(default, rename = "type")]
+    answer_type: AnswerType,
+    question: String,
+    #[serde(default)]
+    options: Vec<DocumentOption>,
+    #[serde(default)]
+    answer: Option<DocumentAnswer>,
+    #[serde(default)]
+    explanation: Option<String>,
+    #[serde(default)]
+    hint: Option<String>,
+    #[serde(default)]
+    media: Option<Media>,
+}
+
+impl DocumentQuestion {
+    fn into_question(self) -> Result<Question, String> {
+        if self.question.trim().is_empty() {
+            return Err("le texte de la question est vide".to_string());
+        }
+
+        let (options, correct_answer_index) = match self.answer_type {
+            AnswerType::TrueFalse => {
+                let options = vec!["Vrai".to_string(), "Faux".to_string()];
+                let correct = match self.answer {
+                    Some(DocumentAnswer::Boolean(answer)) => usize::from(!answer),
+                    _ => return Err("une question vrai/faux attend une réponse true ou false".to_string()),
+                };
+                (options, correct)
+            }
+            AnswerType::Choice => {
+                if self.options.len() < 2 {
+                    return Err("il faut au moins deux options".to_string());
+                }
+                if self.options.iter().any(|o| o.text().trim().is_empty()) {
+                    return Err("une option est vide".to_string());
+                }
+                let marked = self
+                    .options
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, o)| o.is_marked_correct())
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                let correct = match (&self.answer, marked.as_slice()) {
+                    (Some(_), [_, ..]) => return Err("la réponse est donnée à la fois par \"answer\" et par \"correct\"".to_string()),
+                    (None, [index]) => *index,
+                    (None, []) => return Err("aucune bonne réponse n'est indiquée".to_string()),
+                    (None, _) => return Err("plusieurs options sont marquées comme correctes".to_string()),
+                    (Some(DocumentAnswer::Index(index)), _) if *index < self.options.len() => *index,
+                    (Some(DocumentAnswer::Index(index)), _) => {
+                        return Err(format!("la réponse {} ne correspond à aucune option", index))
+                    }
+                    (Some(DocumentAnswer::Text(text)), _) => self
+                        .options
+                        .iter()
+                        .position(|o| o.text() == text)
+                        .ok_or_else(|| format!("la réponse \"{}\" ne fait pas partie des options", text))?,
+                    (Some(DocumentAnswer::Boolean(_)), _) => {
+                        return Err("une réponse true/false est réservée aux questions vrai/faux".to_string())
+                    }
+                };
+                (self.options.iter().map(|o| o.text().to_string()).collect(), correct)
+            }
+        };
+
+        // Multi-line texts keep their inner line breaks but not the surrounding ones
+        Ok(Question {
+            id: self.id,
+            text: self.question.trim().to_string(),
+            options,
+            correct_answer_index,
+            explanation: self.explanation.map(|e| e.trim().to_string()),
+            hint: self.hint.map(|h| h.trim().to_string()),
+            media: self.media,
+        })
+    }
+}
+
+// Scheme of an absolute URL, such as "https" or "data"
+fn scheme(url: &str) -> Option<&str> {
+    url.split_once(':').map(|(scheme, _)| scheme).filter(|scheme| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+// Media are written relative to their bank, like the images of a web page, and shown by the app from
+// its own address: a relative URL is resolved against the bank's. Banks without a folder, such as
+// imported files, keep theirs as written.
+fn resolve_media_url(bank_url: &str, url: &str) -> String {
+    if scheme(url).is_some() {
+        return url.to_string();
+    }
+    let bank_url = bank_url.split(['?', '#']).next().unwrap_or_default();
+    if let Some(path) = url.strip_prefix("//") {
+        return scheme(bank_url).map_or_else(|| url.to_string(), |scheme| format!("{}://{}", scheme, path));
+    }
+    // The bank's site, left out for banks on the app's own site
+    let origin_len = scheme(bank_url).map_or(0, |scheme| {
+        let host = scheme.len() + "://".len();
+        bank_url.get(host..).and_then(|rest| rest.find('/')).map_or(bank_url.len(), |end| host + end)
+    });
+    let (origin, path) = bank_url.split_at(origin_len);
+    if url.starts_with('/') {
+        return format!("{}{}", origin, url);
+    }
+    match path.rfind('/') {
+        Some(folder) => format!("{}{}{}", origin, &path[..=folder], url),
+        None if !origin.is_empty() => format!("{}/{}", origin, url),
+        None => url.to_string(),
+    }
+}
+
+impl BankDocument {
+    // `source` is the URL the bank was loaded from, or the name of the imported file
+    pub fn into_bank(self, source: &str) -> Result<QuestionBank, String> {
+        // Saved quizzes and challenge links find their questions by ID, each must be unique
+        let mut positions = HashMap::new();
+        for (position, question) in self.questions.iter().enumerate() {
+            if let Some(first) = positions.insert(question.id, position + 1) {
+                return Err(format!(
+                    "question {} (id {}) : identifiant déjà utilisé par la question {}",
+                    position + 1,
+                    question.id,
+                    first
+                ));
+            }
+        }
+
+        let questions = self
+            .questions
+            .into_iter()
+            .enumerate()
+            .map(|(position, question)| {
+                let id = question.id;
+                let mut question = question
+                    .into_question()
+                    .map_err(|e| format!("question {} (id {}) : {}", position + 1, id, e))?;
+                if let Some(media) = &mut question.media {
+                    media.url = resolve_media_url(source, &media.url);
+                }
+                Ok(question)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(QuestionBank {
+            title: self.title,
+            grading: self.grading,
+            questions,
+            ..QuestionBank::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bank(json: &str) -> Result<QuestionBank, String> {
+        serde_json::from_str::<BankDocument>(json).unwrap().into_bank("/quiz-app/packs/monuments.json")
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected_with_both_positions() {
+        let error = bank(
+            r#"{ "questions": [
+                { "id": 1, "question": "A ?", "options": ["x", "y"], "answer": 0 },
+                { "id": 2, "question": "B ?", "options": ["x", "y"], "answer": 0 },
+                { "id": 1, "question": "C ?", "options": ["x", "y"], "answer": 1 }
+            ] }"#,
+        )
+        .unwrap_err();
+        assert_eq!(error, "question 3 (id 1) : identifiant déjà utilisé par la question 1");
+    }
+
+    #[test]
+    fn unique_ids_are_accepted() {
+        let loaded = bank(
+            r#"{ "questions": [
+                { "id": 1, "question": "A ?", "options": ["x", "y"], "answer": "y" },
+                { "id": 2, "type": "true_false", "question": "B ?", "answer": true }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(loaded.questions.len(), 2);
+        assert_eq!(loaded.questions[0].correct_answer_index, 1);
+    }
+
+    #[test]
+    fn relative_media_are_resolved_against_the_bank() {
+        let loaded = bank(
+            r#"{ "questions": [{
+                "id": 1, "question": "A ?", "options": ["x", "y"], "answer": 0,
+                "media": { "kind": "image", "url": "media/tour-eiffel.jpg" }
+            }] }"#,
+        )
+        .unwrap();
+        let media = loaded.questions[0].media.as_ref().unwrap();
+        assert_eq!(media.url, "/quiz-app/packs/media/tour-eiffel.jpg");
+    }
+
+    #[test]
+    fn media_urls_resolve_like_links() {
+        let linked = "https://example.org/banques/histoire.json?v=2";
+        assert_eq!(resolve_media_url(linked, "img/a.png"), "https://example.org/banques/img/a.png");
+        assert_eq!(resolve_media_url(linked, "../img/a.png"), "https://example.org/banques/../img/a.png");
+        assert_eq!(resolve_media_url(linked, "/img/a.png"), "https://example.org/img/a.png");
+        assert_eq!(resolve_media_url(linked, "//cdn.example.org/a.png"), "https://cdn.example.org/a.png");
+        assert_eq!(resolve_media_url(linked, "https://cdn.example.org/a.png"), "https://cdn.example.org/a.png");
+        assert_eq!(resolve_media_url(linked, "data:image/png;base64,AAAA"), "data:image/png;base64,AAAA");
+        assert_eq!(resolve_media_url("https://example.org", "a.png"), "https://example.org/a.png");
+        assert_eq!(resolve_media_url("/questions.json", "/img/a.png"), "/img/a.png");
+        assert_eq!(resolve_media_url("banque.json", "img/a.png"), "img/a.png");
+    }
+}
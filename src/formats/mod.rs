@@ -0,0 +1,127 @@
+pub use crate::csv_rows::BankIssue;
+use crate::error::LoadError;
+use crate::grading::Grading;
+use crate::models::{console_log, parse_csv_string, Question};
+use document::BankDocument;
+
+mod document;
+
+// How a question bank file is written
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BankFormat {
+    Csv,
+    Json,
+    // Easier to write by hand: no quoting of commas, and multi-line texts between triple quotes
+    Toml,
+}
+
+impl BankFormat {
+    // The content type sent by the server wins, then the file extension; CSV is the historical default
+    pub fn detect(path: &str, content_type: Option<&str>) -> Self {
+        let content_type = content_type.unwrap_or_default().to_ascii_lowercase();
+        if content_type.contains("json") {
+            return BankFormat::Json;
+        }
+        if content_type.contains("toml") {
+            return BankFormat::Toml;
+        }
+        if content_type.contains("csv") {
+            return BankFormat::Csv;
+        }
+
+        // Ignore any query string or fragment when looking at the extension
+        let path = path.split(['?', '#']).next().unwrap_or_default().to_ascii_lowercase();
+        if path.ends_with(".json") {
+            BankFormat::Json
+        } else if path.ends_with(".toml") {
+            BankFormat::Toml
+        } else {
+            BankFormat::Csv
+        }
+    }
+}
+
+// A question bank as loaded, with what richer formats can carry besides the questions
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuestionBank {
+    pub title: Option<String>,
+    pub grading: Option<Grading>,
+    pub questions: Vec<Question>,
+    // Problems of the rows left out of a CSV bank
+    pub issues: Vec<BankIssue>,
+}
+
+// How many problems are spelled out in an error message, the console has them all
+const ISSUES_IN_ERROR: usize = 5;
+
+// Read a bank from `source`, its URL or file name, which errors mention
+pub fn parse_bank(source: &str, text: &str, format: BankFormat) -> Result<QuestionBank, LoadError> {
+    let parse_error = |message: String| LoadError::Parse {
+        source: source.to_string(),
+        message,
+    };
+    let validation_error = |message: String| LoadError::Validation {
+        source: source.to_string(),
+        message,
+    };
+    match format {
+        BankFormat::Csv => {
+            let (questions, issues) = parse_csv_string(text);
+            for issue in &issues {
+                console_log(&format!("Skipped CSV row, {}", issue));
+            }
+            if questions.is_empty() && !issues.is_empty() {
+                let mut listed = issues.iter().take(ISSUES_IN_ERROR).map(|issue| issue.to_string()).collect::<Vec<_>>();
+                if issues.len() > ISSUES_IN_ERROR {
+                    listed.push(format!("et {} autre(s) problème(s)", issues.len() - ISSUES_IN_ERROR));
+                }
+                return Err(validation_error(format!("{}.", listed.join(" ; "))));
+            }
+            Ok(QuestionBank {
+                questions,
+                issues,
+                ..QuestionBank::default()
+            })
+        }
+        BankFormat::Json => serde_json::from_str::<BankDocument>(text)
+            .map_err(|e| parse_error(format!("JSON invalide : {}", e)))?
+            .into_bank(source)
+            .map_err(validation_error),
+        BankFormat::Toml => toml::from_str::<BankDocument>(text)
+            .map_err(|e| parse_error(format!("TOML invalide : {}", e)))?
+            .into_bank(source)
+            .map_err(validation_error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_type_wins_over_the_extension() {
+        assert_eq!(BankFormat::detect("banque.csv", Some("application/json; charset=utf-8")), BankFormat::Json);
+        assert_eq!(BankFormat::detect("banque.json", Some("application/TOML")), BankFormat::Toml);
+        assert_eq!(BankFormat::detect("banque.toml", Some("text/csv")), BankFormat::Csv);
+    }
+
+    #[test]
+    fn extension_is_used_when_the_content_type_says_nothing() {
+        assert_eq!(BankFormat::detect("packs/histoire.JSON", None), BankFormat::Json);
+        assert_eq!(BankFormat::detect("packs/sciences.toml", Some("text/plain")), BankFormat::Toml);
+        assert_eq!(BankFormat::detect("questions.csv", Some("application/octet-stream")), BankFormat::Csv);
+    }
+
+    #[test]
+    fn query_string_and_fragment_are_ignored() {
+        assert_eq!(BankFormat::detect("https://example.org/banque.json?v=2", None), BankFormat::Json);
+        assert_eq!(BankFormat::detect("https://example.org/banque.toml#questions", None), BankFormat::Toml);
+        assert_eq!(BankFormat::detect("https://example.org/banque?format=.json", None), BankFormat::Csv);
+    }
+
+    #[test]
+    fn csv_is_the_default() {
+        assert_eq!(BankFormat::detect("https://example.org/banque", None), BankFormat::Csv);
+        assert_eq!(BankFormat::detect("", None), BankFormat::Csv);
+    }
+}
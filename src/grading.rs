@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use crate::models::{console_log, fetch_text, get_base_url};
+use crate::packs::Pack;
+
+// Message shown on the results from a given percentage of right answers upwards
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBand {
+    pub min_percentage: usize,
+    pub emoji: String,
+    pub message: String,
+}
+
+impl ScoreBand {
+    fn new(min_percentage: usize, emoji: &str, message: &str) -> Self {
+        Self {
+            min_percentage,
+            emoji: emoji.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// Confetti rain on the results, for scores at or above the threshold
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Celebration {
+    #[serde(default = "default_confetti")]
+    pub confetti: bool,
+    #[serde(default = "default_confetti_threshold")]
+    pub min_percentage: usize,
+}
+
+fn default_confetti() -> bool {
+    true
+}
+
+fn default_confetti_threshold() -> usize {
+    70
+}
+
+impl Default for Celebration {
+    fn default() -> Self {
+        Self {
+            confetti: default_confetti(),
+            min_percentage: default_confetti_threshold(),
+        }
+    }
+}
+
+fn default_bands() -> Vec<ScoreBand> {
+    vec![
+        ScoreBand::new(90, "🏆", "Excellent! Vous êtes un expert!"),
+        ScoreBand::new(70, "🎉", "Très bien! Vous avez d'excellentes connaissances!"),
+        ScoreBand::new(50, "👍", "Bien! Vous avez de bonnes connaissances."),
+        ScoreBand::new(30, "🤔", "Pas mal. Continuez à apprendre!"),
+        ScoreBand::new(0, "📚", "Continuez à apprendre, vous progressez!"),
+    ]
+}
+
+// How a quiz grades its results. Each question bank can bring its own, any field left out
+// falls back to the defaults below.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Grading {
+    // Percentage needed to pass; the pass mark from the settings applies when the quiz sets none
+    #[serde(default)]
+    pub pass_mark: Option<usize>,
+    #[serde(default = "default_bands")]
+    pub bands: Vec<ScoreBand>,
+    #[serde(default)]
+    pub celebration: Celebration,
+}
+
+impl Default for Grading {
+    fn default() -> Self {
+        Self {
+            pass_mark: None,
+            bands: default_bands(),
+            celebration: Celebration::default(),
+        }
+    }
+}
+
+impl Grading {
+    // The highest band reached, whatever order the bands are listed in
+    pub fn band(&self, percentage: usize) -> Option<&ScoreBand> {
+        self.bands
+            .iter()
+            .filter(|band| band.min_percentage <= percentage)
+            .max_by_key(|band| band.min_percentage)
+    }
+
+    pub fn celebrates(&self, percentage: usize) -> bool {
+        self.celebration.confetti && percentage >= self.celebration.min_percentage
+    }
+}
+
+// Grading of a pack in packs.json, for banks that cannot carry one such as CSV banks: either written
+// in the manifest, or the path of a file like grading.json, relative to the app or an absolute URL
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum PackGrading {
+    Inline(Grading),
+    File(String),
+}
+
+impl PackGrading {
+    pub async fn load(&self) -> Grading {
+        match self {
+            PackGrading::Inline(grading) => grading.clone(),
+            PackGrading::File(path) if Pack::is_url(path) => fetch_grading(path).await,
+            PackGrading::File(path) => fetch_grading(&format!("{}{}", get_base_url(), path)).await,
+        }
+    }
+}
+
+// Load the grading served next to the question bank; the default grading applies when there is none
+pub async fn load_grading() -> Grading {
+    fetch_grading(&format!("{}grading.json", get_base_url())).await
+}
+
+async fn fetch_grading(url: &str) -> Grading {
+    let grading = match fetch_text(url).await {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}", e)),
+        Err(e) => Err(e.to_string()),
+    };
+    grading.unwrap_or_else(|e| {
+        console_log(&format!("Using the default grading instead of {} ({})", url, e));
+        Grading::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_grading_is_inline_or_a_file() {
+        let inline = serde_json::from_str::<PackGrading>(r#"{ "pass_mark": 60 }"#).unwrap();
+        assert_eq!(
+            inline,
+            PackGrading::Inline(Grading {
+                pass_mark: Some(60),
+                ..Grading::default()
+            })
+        );
+        let file = serde_json::from_str::<PackGrading>(r#""packs/histoire-grading.json""#).unwrap();
+        assert_eq!(file, PackGrading::File("packs/histoire-grading.json".to_string()));
+    }
+
+    #[test]
+    fn band_is_the_highest_one_reached() {
+        let grading = Grading::default();
+        let emoji = |percentage| grading.band(percentage).map(|band| band.emoji.as_str());
+        assert_eq!(emoji(0), Some("📚"));
+        assert_eq!(emoji(29), Some("📚"));
+        assert_eq!(emoji(30), Some("🤔"));
+        assert_eq!(emoji(49), Some("🤔"));
+        assert_eq!(emoji(50), Some("👍"));
+        assert_eq!(emoji(69), Some("👍"));
+        assert_eq!(emoji(70), Some("🎉"));
+        assert_eq!(emoji(89), Some("🎉"));
+        assert_eq!(emoji(90), Some("🏆"));
+        assert_eq!(emoji(100), Some("🏆"));
+    }
+
+    #[test]
+    fn bands_can_be_listed_in_any_order_and_leave_low_scores_out() {
+        let grading = Grading {
+            bands: vec![ScoreBand::new(50, "👍", "Bien"), ScoreBand::new(80, "🏆", "Excellent")],
+            ..Grading::default()
+        };
+        assert_eq!(grading.band(49), None);
+        assert_eq!(grading.band(79).map(|band| band.min_percentage), Some(50));
+        assert_eq!(grading.band(80).map(|band| band.min_percentage), Some(80));
+    }
+
+    #[test]
+    fn confetti_from_the_threshold_up_unless_turned_off() {
+        let mut grading = Grading::default();
+        assert!(!grading.celebrates(69));
+        assert!(grading.celebrates(70));
+        assert!(grading.celebrates(100));
+        grading.celebration.confetti = false;
+        assert!(!grading.celebrates(100));
+    }
+
+    #[test]
+    fn fields_left_out_fall_back_to_the_defaults() {
+        let grading = serde_json::from_str::<Grading>(r#"{ "celebration": { "min_percentage": 90 } }"#).unwrap();
+        assert_eq!(grading.bands, default_bands());
+        assert!(grading.celebration.confetti);
+        assert!(!grading.celebrates(89));
+        assert!(grading.celebrates(90));
+    }
+}
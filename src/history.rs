@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use crate::clock;
+use crate::session::{QuizMode, QuizSession};
+use crate::storage::{self, HISTORY_KEY};
+
+// Older games are dropped once the history grows past this
+const MAX_HISTORY: usize = 50;
+
+// Summary of a finished quiz, kept locally for the statistics page
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuizRecord {
+    pub finished_at: f64,
+    pub mode: QuizMode,
+    pub score: usize,
+    pub answered: usize,
+    pub time_ms: f64,
+}
+
+pub fn load_history() -> Vec<QuizRecord> {
+    storage::load(HISTORY_KEY).unwrap_or_default()
+}
+
+pub fn record(session: &QuizSession) {
+    let mut history = load_history();
+    history.push(QuizRecord {
+        finished_at: clock::now(),
+        mode: session.mode,
+        score: session.score(),
+        answered: session.answered_count(),
+        time_ms: session.progress.iter().map(|p| p.time_ms).sum(),
+    });
+    if history.len() > MAX_HISTORY {
+        history.drain(..history.len() - MAX_HISTORY);
+    }
+    storage::save(HISTORY_KEY, &history);
+}
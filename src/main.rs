@@ -1,283 +1,1057 @@
 use yew::prelude::*;
+use yew_router::prelude::*;
 use wasm_bindgen_futures::spawn_local;
-use models::{get_random_question_sequence_from_list, get_fallback_questions, load_questions_from_csv, console_log};
-use components::QuestionComponent;
+use models::{get_base_url, get_random_question_sequence_from_list, get_shuffled_questions, get_fallback_questions, load_question_bank_with_retries, console_log, LOAD_ATTEMPTS, Question};
+use error::LoadError;
+use formats::{BankIssue, QuestionBank};
+use grading::{load_grading, Grading};
+use packs::{fallback_pack_id, load_packs, Pack, PackSource};
+use serde::Deserialize;
+use std::collections::HashMap;
+use components::{BankImport, BankReport, ChallengePage, QuestionComponent, QuestionNavigator, ResultsReview, ResultsScreen, SettingsPage, SharedResultPage, ShortcutHelp, StartScreen, StatsPage};
+use routes::Route;
+use session::{Confidence, QuizMode, QuizOptions, QuizSession, SavedQuiz, SURVIVAL_LIVES};
+use share::{Challenge, SharedResult};
+use shortcuts::Shortcut;
+use storage::{ACTIVE_QUIZ_KEY, LAST_RESULT_KEY, SELECTED_PACK_KEY, SETTINGS_KEY, SURVIVAL_BEST_KEY};
 
+mod certificate;
+mod clock;
 mod models;
 mod components;
+mod csv_rows;
+mod error;
+mod formats;
+mod grading;
+mod history;
+mod offline;
+mod packs;
+mod routes;
+mod session;
+mod share;
+mod shortcuts;
+mod storage;
 
 const QUIZ_SIZE: usize = 10;
 
-#[derive(Clone, PartialEq)]
-enum QuizState {
-    Loading,
-    InProgress,
-    Completed,
+// Any page can be opened on a given pack with `?pack=<id>`, which share and challenge links rely on,
+// or on any bank served with CORS with `?bank=<url>`
+#[derive(Deserialize)]
+struct PackQuery {
+    #[serde(default)]
+    pack: Option<String>,
+    #[serde(default)]
+    bank: Option<String>,
+}
+
+// Draw a fresh quiz from the bank for the given mode
+fn new_session(mode: QuizMode, options: QuizOptions, grading: &Grading, pack: &str, bank: &[Question], seed: u64) -> QuizSession {
+    let mut session = match mode {
+        QuizMode::Classic => QuizSession::new(get_random_question_sequence_from_list(bank, QUIZ_SIZE, seed)),
+        QuizMode::Practice => QuizSession::practice(get_random_question_sequence_from_list(bank, QUIZ_SIZE, seed)),
+        QuizMode::Survival => QuizSession::survival(get_shuffled_questions(bank, seed)),
+    };
+    session.options = options;
+    session.grading = grading.clone();
+    session.pack = pack.to_string();
+    session.seed = seed;
+    session
 }
 
 #[function_component(App)]
 fn app() -> Html {
+    let route = use_route::<Route>().unwrap_or(Route::NotFound);
+    let navigator = use_navigator().expect("App is rendered inside the router");
+    let location = use_location();
+    let packs = use_state(Vec::<Pack>::new);
+    // The pack picked, and the one whose questions are loaded, which differ while it loads
+    let pack_id = use_state(|| None::<String>);
+    let loaded_pack = use_state(|| None::<String>);
+    // Banks read from files of the player, kept in memory for as long as the page is open
+    let imported = use_state(HashMap::<String, QuestionBank>::new);
     let questions = use_state(Vec::new);
-    let random_questions = use_state(Vec::new);
-    let current_question = use_state(|| 0);
-    let selected_answers = use_state(|| vec![None; QUIZ_SIZE]);
-    let is_submitted = use_state(|| false);
-    let quiz_state = use_state(|| QuizState::Loading);
-    let loading_error = use_state(|| false);
-    
-    // Load questions when the component mounts - only once
+    let grading = use_state(Grading::default);
+    let session = use_state(QuizSession::default);
+    let loading = use_state(|| true);
+    // Why the picked pack could not be loaded, while the player chose to play the fallback questions instead
+    let loading_error = use_state(|| None::<LoadError>);
+    // Rows of the loaded bank that were left out
+    let bank_issues = use_state(Vec::<BankIssue>::new);
+    // Why the picked pack could not be loaded, shown until the player tries again or picks something else
+    let bank_error = use_state(|| None::<LoadError>);
+    // Shown while loading, from the first retry on
+    let load_attempt = use_state(|| 1u32);
+    // Bumped for every pick, so a load that finishes after another pack was picked is dropped
+    let load_generation = use_mut_ref(|| 0u64);
+    // Bumped to load the picked pack again after a failure
+    let reloads = use_state(|| 0u32);
+    let resumable = use_state(|| None::<SavedQuiz>);
+    let settings = use_state(|| storage::load::<QuizOptions>(SETTINGS_KEY).unwrap_or_default());
+    let survival_best = use_state(|| storage::load::<usize>(SURVIVAL_BEST_KEY).unwrap_or(0));
+    let new_record = use_state(|| false);
+    let show_shortcuts = use_state(|| false);
+    // Set when the service worker cached a newer version of a bank, played from the next page load
+    let bank_update = use_state(|| false);
+    let question_ref = use_node_ref();
+    let next_ref = use_node_ref();
+    let retry_ref = use_node_ref();
+    
+    // Load the list of packs when the component mounts - only once - and pick the one to load: the pack
+    // of the quiz or result being reopened, the one a link asks for, or else the one picked last time
     {
-        let questions = questions.clone();
-        let random_questions = random_questions.clone();
-        let quiz_state = quiz_state.clone();
-        let loading_error = loading_error.clone();
+        let packs = packs.clone();
+        let pack_id = pack_id.clone();
+        let initial_route = route.clone();
+        let query = location.as_ref().and_then(|location| location.query::<PackQuery>().ok());
+        let (linked_bank, linked_pack) = match query {
+            Some(query) => (query.bank, query.pack),
+            None => (None, None),
+        };
 
         use_effect_with(
             (), move |_| {
-                let questions_clone = questions.clone();
-                let random_questions_clone = random_questions.clone();
-                let quiz_state_clone = quiz_state.clone();
-                let loading_error_clone = loading_error.clone();
-
                 spawn_local(async move {
-                    console_log("Starting to load questions...");
-                    // Only attempt to load if we haven't loaded questions yet
-                    if questions_clone.len() == 0 {
-                        match load_questions_from_csv().await {
-                            Ok(loaded_questions) => {
-                                console_log(&format!("Loaded {} questions from CSV", loaded_questions.len()));
-                                questions_clone.set(loaded_questions.clone());
-                                if loaded_questions.len() >= QUIZ_SIZE {
-                                    let random_qs = get_random_question_sequence_from_list(&loaded_questions, QUIZ_SIZE);
-                                    random_questions_clone.set(random_qs);
-                                    quiz_state_clone.set(QuizState::InProgress);
-                                } else {
-                                    console_log("Not enough questions loaded from CSV, using fallback");
-                                    loading_error_clone.set(true);
-                                    let fallback = get_fallback_questions();
-                                    questions_clone.set(fallback.clone());
-                                    let random_qs = get_random_question_sequence_from_list(&fallback, QUIZ_SIZE);
-                                    random_questions_clone.set(random_qs);
-                                    quiz_state_clone.set(QuizState::InProgress);
+                    let mut available = load_packs().await;
+                    let reopened = match initial_route {
+                        Route::Quiz => storage::load::<SavedQuiz>(ACTIVE_QUIZ_KEY).map(|saved| saved.pack),
+                        Route::Results | Route::Review => storage::load::<SavedQuiz>(LAST_RESULT_KEY).map(|saved| saved.pack),
+                        _ => None,
+                    };
+                    // A bank asked for by a link is always the one loaded, whatever its address looks like
+                    let initial = linked_bank
+                        .map(|bank| Pack::resolve_url(&bank))
+                        .or_else(|| {
+                            [reopened, linked_pack, storage::load::<String>(SELECTED_PACK_KEY)]
+                                .into_iter()
+                                .flatten()
+                                .find(|id| available.iter().any(|pack| pack.id == *id) || Pack::is_url(id))
+                        })
+                        .unwrap_or_else(|| available[0].id.clone());
+                    // Banks from links are IDed by their URL and added to the picker
+                    if !available.iter().any(|pack| pack.id == initial) {
+                        available.push(Pack::from_url(&initial));
+                    }
+                    packs.set(available);
+                    pack_id.set(Some(initial));
+                });
+                
+                // Clean-up function (important to avoid memory leaks)
+                || ()
+            },
+        );
+    }
+    
+    // Load the questions of the picked pack, each time another pack is picked
+    {
+        let packs = packs.clone();
+        let questions = questions.clone();
+        let grading = grading.clone();
+        let loaded_pack = loaded_pack.clone();
+        let session = session.clone();
+        let loading = loading.clone();
+        let loading_error = loading_error.clone();
+        let bank_issues = bank_issues.clone();
+        let bank_error = bank_error.clone();
+        let load_attempt = load_attempt.clone();
+        let resumable = resumable.clone();
+        let load_generation = load_generation.clone();
+        let current_route = route.clone();
+
+        use_effect_with(
+            ((*pack_id).clone(), (*imported).clone(), *reloads), move |(pack_id, imported, _)| {
+                let pack = pack_id
+                    .as_ref()
+                    .filter(|id| loaded_pack.as_ref() != Some(*id))
+                    .and_then(|id| packs.iter().find(|pack| pack.id == *id))
+                    .cloned();
+                
+                *load_generation.borrow_mut() += 1;
+                let generation = *load_generation.borrow();
+                let is_current = move || *load_generation.borrow() == generation;
+                
+                // Back on the pack already loaded while another one was loading
+                if pack.is_none() && pack_id.is_some() && loaded_pack.as_ref() == pack_id.as_ref() {
+                    loading.set(false);
+                }
+                
+                if let Some(pack) = pack {
+                    loading.set(true);
+                    let imported = imported.get(&pack.id).cloned();
+                    load_attempt.set(1);
+                    spawn_local(async move {
+                        console_log(&format!("Starting to load the questions of {}...", pack.id));
+                        let bank_url = pack.url();
+                        let loaded = match imported {
+                            Some(bank) => Ok(bank),
+                            None => {
+                                let on_retry = |attempt| {
+                                    if is_current() {
+                                        load_attempt.set(attempt);
+                                    }
+                                };
+                                load_question_bank_with_retries(&bank_url, on_retry).await
+                            }
+                        };
+                        if !is_current() {
+                            console_log(&format!("Dropping the questions of {}, another pack was picked", pack.id));
+                            return;
+                        }
+                        
+                        // A listed pack needs enough questions for a quiz, a bank from a link or a file is played as it is
+                        let needed = if pack.source == PackSource::Manifest { QUIZ_SIZE } else { 1 };
+                        let loaded = loaded.and_then(|loaded| match loaded.questions.len() {
+                            found if found < needed => Err(LoadError::InsufficientQuestions {
+                                source: bank_url.clone(),
+                                found,
+                                needed,
+                            }),
+                            _ => Ok(loaded),
+                        });
+                        // Nothing else is played without the player asking for it
+                        let loaded = match loaded {
+                            Ok(loaded) => loaded,
+                            Err(error) => {
+                                console_log(&format!("Error loading questions from {}: {}", bank_url, error));
+                                bank_error.set(Some(error));
+                                loading.set(false);
+                                return;
+                            }
+                        };
+                        console_log(&format!("Loaded {} questions from {}", loaded.questions.len(), bank_url));
+                        // A bank that brings its own grading, or whose pack sets one, does not need grading.json
+                        let bank_grading = match (loaded.grading, &pack.grading) {
+                            (Some(bank_grading), _) => bank_grading,
+                            (None, Some(pack_grading)) => pack_grading.load().await,
+                            (None, None) => load_grading().await,
+                        };
+                        if !is_current() {
+                            return;
+                        }
+                        bank_error.set(None);
+                        loading_error.set(None);
+                        bank_issues.set(loaded.issues);
+                        let bank = loaded.questions;
+                        questions.set(bank.clone());
+                        loaded_pack.set(Some(pack.id.clone()));
+                        grading.set(bank_grading);
+                        
+                        // Pick up a quiz of this pack interrupted by a reload, as long as it still matches the bank:
+                        // directly when the page was reloaded on the quiz itself, otherwise through an offer on the
+                        // home page. Quizzes saved before packs existed are for the first pack.
+                        let of_pack = |saved: &SavedQuiz| saved.pack == pack.id || saved.pack.is_empty();
+                        let saved = storage::load::<SavedQuiz>(ACTIVE_QUIZ_KEY).filter(of_pack);
+                        let restored = saved.clone().and_then(|saved| QuizSession::from_saved(saved, &bank));
+                        if saved.is_some() && restored.is_none() {
+                            storage::remove(ACTIVE_QUIZ_KEY);
+                        }
+                        // The offer keeps the saved quiz, rebuilt only once picked up so the clock does not run meanwhile
+                        let offered = saved.filter(|_| restored.is_some());
+                        match current_route {
+                            Route::Quiz => {
+                                if let Some(restored) = restored {
+                                    session.set(restored);
                                 }
                             }
-                            Err(e) => {
-                                console_log(&format!("Error loading questions from CSV: {:?}", e));
-                                loading_error_clone.set(true);
-                                let fallback = get_fallback_questions();
-                                questions_clone.set(fallback.clone());
-                                let random_qs = get_random_question_sequence_from_list(&fallback, QUIZ_SIZE);
-                                random_questions_clone.set(random_qs);
-                                quiz_state_clone.set(QuizState::InProgress);
+                            // Bookmarked results show the last finished quiz
+                            Route::Results | Route::Review => {
+                                let last = storage::load::<SavedQuiz>(LAST_RESULT_KEY)
+                                    .filter(of_pack)
+                                    .and_then(|last| QuizSession::from_saved(last, &bank));
+                                if let Some(mut last) = last {
+                                    last.finished = true;
+                                    session.set(last);
+                                }
+                                resumable.set(offered);
                             }
+                            _ => resumable.set(offered),
                         }
-                    }
-                });
+                        loading.set(false);
+                    });
+                }
                 
-                // Clean-up function (important to avoid memory leaks)
                 || ()
             },
         );
     }
     
-    let current_question_index = *current_question;
+    // Keep the quiz in progress in localStorage after every action. A quiz resumed from memory after
+    // picking another pack is saved again once back on its own pack.
+    {
+        let questions = questions.clone();
+        let loaded_pack = loaded_pack.clone();
+        use_effect_with(
+            (*session).clone(),
+            move |session| {
+                if !session.is_empty() && !session.finished && loaded_pack.as_ref() == Some(&session.pack) {
+                    storage::save(ACTIVE_QUIZ_KEY, &session.to_saved(&questions));
+                }
+                || ()
+            },
+        );
+    }
+    
+    // Once every question has been dealt with: forget the quiz in progress, keep its results for the
+    // results page and the statistics, and show them
+    let on_finished = {
+        let questions = questions.clone();
+        let session = session.clone();
+        let survival_best = survival_best.clone();
+        let new_record = new_record.clone();
+        let navigator = navigator.clone();
+        
+        Callback::from(move |mut finished: QuizSession| {
+            finished.finished = true;
+            storage::remove(ACTIVE_QUIZ_KEY);
+            storage::save(LAST_RESULT_KEY, &finished.to_saved(&questions));
+            history::record(&finished);
+            if finished.mode == QuizMode::Survival && finished.score() > *survival_best {
+                storage::save(SURVIVAL_BEST_KEY, &finished.score());
+                survival_best.set(finished.score());
+                new_record.set(true);
+            }
+            session.set(finished);
+            navigator.push(&Route::Results);
+        })
+    };
+    
     let on_option_select = {
-        let selected_answers = selected_answers.clone();
+        let session = session.clone();
         Callback::from(move |option_index: usize| {
-            let mut new_answers = (*selected_answers).clone();
-            new_answers[current_question_index] = Some(option_index);
-            selected_answers.set(new_answers);
+            let mut updated = (*session).clone();
+            updated.select(option_index);
+            session.set(updated);
+        })
+    };
+    
+    let on_confidence = {
+        let session = session.clone();
+        Callback::from(move |confidence: Confidence| {
+            let mut updated = (*session).clone();
+            updated.set_confidence(confidence);
+            session.set(updated);
         })
     };
     
     let on_submit = {
-        let is_submitted = is_submitted.clone();
+        let session = session.clone();
+        Callback::from(move |_: ()| {
+            let mut updated = (*session).clone();
+            updated.submit();
+            session.set(updated);
+        })
+    };
+    
+    let on_resume = {
+        let questions = questions.clone();
+        let session = session.clone();
+        let resumable = resumable.clone();
+        let navigator = navigator.clone();
+        
         Callback::from(move |_| {
-            is_submitted.set(true);
+            if let Some(saved) = (*resumable).clone().and_then(|saved| QuizSession::from_saved(saved, &questions)) {
+                session.set(saved);
+            }
+            resumable.set(None);
+            navigator.push(&Route::Quiz);
         })
     };
     
+    // Moving on, skipping and flagging all go to the next question still waiting for an answer,
+    // and only end the quiz once every skipped or flagged question has been revisited
     let on_next = {
-        let current_question = current_question.clone();
-        let is_submitted = is_submitted.clone();
-        let quiz_state = quiz_state.clone();
+        let session = session.clone();
+        let on_finished = on_finished.clone();
+        
+        Callback::from(move |_: ()| {
+            let mut updated = (*session).clone();
+            if updated.advance() {
+                session.set(updated);
+            } else {
+                on_finished.emit(updated);
+            }
+        })
+    };
+    
+    let on_skip = {
+        let session = session.clone();
+        let on_finished = on_finished.clone();
         
         Callback::from(move |_| {
-            let next_index = *current_question + 1;
-            if next_index < QUIZ_SIZE {
-                current_question.set(next_index);
-                is_submitted.set(false);
+            let mut updated = (*session).clone();
+            if updated.skip() {
+                session.set(updated);
             } else {
-                quiz_state.set(QuizState::Completed);
+                on_finished.emit(updated);
             }
         })
     };
     
-    let on_retry = {
-        let questions = questions.clone();
-        let random_questions = random_questions.clone();
-        let current_question = current_question.clone();
-        let selected_answers = selected_answers.clone();
-        let is_submitted = is_submitted.clone();
-        let quiz_state = quiz_state.clone();
+    let on_flag = {
+        let session = session.clone();
+        let on_finished = on_finished.clone();
+        
+        Callback::from(move |_| {
+            let mut updated = (*session).clone();
+            if updated.flag() {
+                session.set(updated);
+            } else {
+                on_finished.emit(updated);
+            }
+        })
+    };
+    
+    // Skipping the last question left would only bring it back, the player can still end the quiz without it
+    let on_finish_unanswered = {
+        let session = session.clone();
+        let on_finished = on_finished.clone();
         
         Callback::from(move |_| {
+            let mut updated = (*session).clone();
+            updated.finish_unanswered();
+            on_finished.emit(updated);
+        })
+    };
+    
+    let on_fifty_fifty = {
+        let session = session.clone();
+        Callback::from(move |_| {
+            let mut updated = (*session).clone();
+            updated.use_fifty_fifty();
+            session.set(updated);
+        })
+    };
+    
+    let on_hint = {
+        let session = session.clone();
+        Callback::from(move |_| {
+            let mut updated = (*session).clone();
+            updated.use_hint();
+            session.set(updated);
+        })
+    };
+    
+    let on_jump = {
+        let session = session.clone();
+        Callback::from(move |index: usize| {
+            let mut updated = (*session).clone();
+            updated.go_to(index);
+            session.set(updated);
+        })
+    };
+    
+    let on_start = {
+        let questions = questions.clone();
+        let grading = grading.clone();
+        let loaded_pack = loaded_pack.clone();
+        let session = session.clone();
+        let resumable = resumable.clone();
+        let settings = settings.clone();
+        let new_record = new_record.clone();
+        let navigator = navigator.clone();
+        
+        Callback::from(move |mode: QuizMode| {
+            resumable.set(None);
+            new_record.set(false);
             // We already have the questions loaded, just need to get a new random sequence
-            random_questions.set(get_random_question_sequence_from_list(&questions, QUIZ_SIZE));
-            current_question.set(0);
-            selected_answers.set(vec![None; QUIZ_SIZE]);
-            is_submitted.set(false);
-            quiz_state.set(QuizState::InProgress);
+            session.set(new_session(mode, (*settings).clone(), &grading, loaded_pack.as_deref().unwrap_or_default(), &questions, rand::random()));
+            navigator.push(&Route::Quiz);
+        })
+    };
+    
+    // Someone else's quiz is drawn again from their seed, with their configuration
+    let on_play_shared = {
+        let questions = questions.clone();
+        let grading = grading.clone();
+        let loaded_pack = loaded_pack.clone();
+        let session = session.clone();
+        let resumable = resumable.clone();
+        let new_record = new_record.clone();
+        let navigator = navigator.clone();
+        
+        Callback::from(move |shared: SharedResult| {
+            resumable.set(None);
+            new_record.set(false);
+            session.set(new_session(shared.mode, shared.options(), &grading, loaded_pack.as_deref().unwrap_or_default(), &questions, shared.seed));
+            navigator.push(&Route::Quiz);
+        })
+    };
+    
+    let on_retry = {
+        let mode = session.mode;
+        on_start.reform(move |_: ()| mode)
+    };
+    
+    let on_accept_challenge = {
+        let grading = grading.clone();
+        let loaded_pack = loaded_pack.clone();
+        let session = session.clone();
+        let resumable = resumable.clone();
+        let new_record = new_record.clone();
+        let navigator = navigator.clone();
+        
+        Callback::from(move |mut challenge: QuizSession| {
+            resumable.set(None);
+            new_record.set(false);
+            // The clock starts when the challenge is accepted, not when its page was shown
+            challenge.question_started_at = clock::now();
+            challenge.grading = (*grading).clone();
+            challenge.pack = (*loaded_pack).clone().unwrap_or_default();
+            session.set(challenge);
+            navigator.push(&Route::Quiz);
+        })
+    };
+    
+    // The questions of the new pack are loaded right away, and it is picked again on the next visit
+    let on_select_pack = {
+        let pack_id = pack_id.clone();
+        Callback::from(move |id: String| {
+            storage::save(SELECTED_PACK_KEY, &id);
+            pack_id.set(Some(id));
+        })
+    };
+    
+    // An imported file becomes the current pack straight away, importing it again reloads it
+    let on_import = {
+        let packs = packs.clone();
+        let imported = imported.clone();
+        let pack_id = pack_id.clone();
+        let loaded_pack = loaded_pack.clone();
+        Callback::from(move |(name, bank): (String, QuestionBank)| {
+            let pack = Pack::from_file(&name, bank.title.as_deref(), bank.questions.len());
+            let mut updated = (*packs).clone();
+            updated.retain(|listed| listed.id != pack.id);
+            updated.push(pack.clone());
+            packs.set(updated);
+            let mut banks = (*imported).clone();
+            banks.insert(pack.id.clone(), bank);
+            imported.set(banks);
+            loaded_pack.set(None);
+            pack_id.set(Some(pack.id));
+        })
+    };
+    
+    // Back to the other packs of the app after the picked one failed to load
+    let on_leave_bank = {
+        let packs = packs.clone();
+        let pack_id = pack_id.clone();
+        let bank_error = bank_error.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            let stored = storage::load::<String>(SELECTED_PACK_KEY);
+            let listed = packs
+                .iter()
+                .filter(|pack| pack.source == PackSource::Manifest && Some(&pack.id) != pack_id.as_ref())
+                .collect::<Vec<_>>();
+            let fallback = listed
+                .iter()
+                .find(|pack| Some(&pack.id) == stored.as_ref())
+                .or(listed.first())
+                .map(|pack| pack.id.clone());
+            bank_error.set(None);
+            pack_id.set(fallback);
+            navigator.push(&Route::Home);
         })
     };
     
-    // Nothing here - removing unused code
+    // The built-in questions stand in for the picked pack, only once the player has asked for them. They
+    // are played as the pack of the default bank they come from, the picked pack stays picked to try again.
+    let on_play_fallback = {
+        let packs = packs.clone();
+        let loaded_pack = loaded_pack.clone();
+        let questions = questions.clone();
+        let grading = grading.clone();
+        let bank_issues = bank_issues.clone();
+        let bank_error = bank_error.clone();
+        let loading_error = loading_error.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            questions.set(get_fallback_questions());
+            bank_issues.set(Vec::new());
+            loaded_pack.set(Some(fallback_pack_id(&packs)));
+            loading_error.set((*bank_error).clone());
+            bank_error.set(None);
+            navigator.push(&Route::Home);
+            let grading = grading.clone();
+            spawn_local(async move {
+                grading.set(load_grading().await);
+            });
+        })
+    };
+    
+    // Load the picked pack again, for failures that may not happen twice
+    let on_reload_bank = {
+        let loaded_pack = loaded_pack.clone();
+        let reloads = reloads.clone();
+        Callback::from(move |_| {
+            loaded_pack.set(None);
+            reloads.set(*reloads + 1);
+        })
+    };
+    
+    let on_settings_change = {
+        let settings = settings.clone();
+        Callback::from(move |options: QuizOptions| {
+            storage::save(SETTINGS_KEY, &options);
+            settings.set(options);
+        })
+    };
+    
+    let on_toggle_help = {
+        let show_shortcuts = show_shortcuts.clone();
+        Callback::from(move |_: ()| show_shortcuts.set(!*show_shortcuts))
+    };
+    
+    // The whole quiz can be played from the keyboard, see `shortcuts::SHORTCUT_HELP`
+    let on_keydown = {
+        let session = session.clone();
+        let route = route.clone();
+        let show_shortcuts = show_shortcuts.clone();
+        let on_option_select = on_option_select.clone();
+        let on_submit = on_submit.clone();
+        let on_next = on_next.clone();
+        let on_retry = on_retry.clone();
+        let on_toggle_help = on_toggle_help.clone();
+        
+        Callback::from(move |event: KeyboardEvent| {
+            if shortcuts::is_handled_by_target(&event) {
+                return;
+            }
+            let Some(shortcut) = shortcuts::shortcut_for(&event) else {
+                return;
+            };
+            let in_progress = route == Route::Quiz && !session.is_empty() && !session.finished;
+            match shortcut {
+                Shortcut::ToggleHelp => on_toggle_help.emit(()),
+                Shortcut::CloseHelp if *show_shortcuts => show_shortcuts.set(false),
+                Shortcut::SelectOption(index) if in_progress && !session.current_progress().submitted => {
+                    let progress = session.current_progress();
+                    let available = index < session.current_question().options.len()
+                        && !progress.removed_options.contains(&index)
+                        && !progress.wrong_attempts.contains(&index);
+                    if available {
+                        on_option_select.emit(index);
+                    }
+                }
+                Shortcut::Confirm if in_progress => {
+                    if session.current_progress().submitted {
+                        on_next.emit(());
+                    } else if session.can_submit() {
+                        on_submit.emit(());
+                    }
+                }
+                Shortcut::Retry if route == Route::Results && session.finished => on_retry.emit(()),
+                _ => return,
+            }
+            event.prevent_default();
+        })
+    };
+    shortcuts::use_document_keydown(on_keydown);
+    
+    let on_bank_update = {
+        let bank_update = bank_update.clone();
+        Callback::from(move |url: String| {
+            console_log(&format!("A newer version of {} is available", url));
+            bank_update.set(true);
+        })
+    };
+    offline::use_bank_updates(on_bank_update);
+    
+    // The quiz in progress is saved, reloading picks it up again with the new questions where they still match
+    let on_reload_page = Callback::from(|_| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().reload();
+        }
+    });
+    
+    let on_dismiss_update = {
+        let bank_update = bank_update.clone();
+        Callback::from(move |_| bank_update.set(false))
+    };
+    
+    // Move the focus along with the quiz: the question heading once it is shown, the next button once
+    // the answer is revealed, and the retry button on the results
+    {
+        let question_ref = question_ref.clone();
+        let next_ref = next_ref.clone();
+        let retry_ref = retry_ref.clone();
+        let submitted = !session.is_empty() && session.current_progress().submitted;
+        use_effect_with(
+            (route.clone(), *loading, session.current, submitted),
+            move |(route, _, _, submitted)| {
+                match route {
+                    Route::Quiz if *submitted => shortcuts::focus(&next_ref),
+                    Route::Quiz => shortcuts::focus(&question_ref),
+                    Route::Results => shortcuts::focus(&retry_ref),
+                    _ => {}
+                }
+                || ()
+            },
+        );
+    }
+    
+    let quiz_title = packs
+        .iter()
+        .find(|pack| pack.id == session.pack)
+        .map(|pack| pack.title.clone())
+        .unwrap_or_else(|| "Culture générale".to_string());
+    
+    let page = match route {
+        Route::Stats => html! { <StatsPage /> },
+        Route::Settings => html! { <SettingsPage options={(*settings).clone()} on_change={on_settings_change} /> },
+        Route::NotFound => html! { <Redirect<Route> to={Route::Home} /> },
+        _ if *loading => {
+            html! {
+                <div class="loading-container">
+                    <div class="loading-spinner"></div>
+                    <p>{ "Chargement des questions..." }</p>
+                    if *load_attempt > 1 {
+                        <p>{ format!("Nouvelle tentative ({}/{})…", *load_attempt, LOAD_ATTEMPTS) }</p>
+                    }
+                </div>
+            }
+        },
+        _ if bank_error.is_some() => {
+            let error = (*bank_error).clone();
+            let source = packs.iter().find(|pack| Some(&pack.id) == pack_id.as_ref()).map(|pack| pack.source);
+            // The built-in questions are general knowledge, they can only stand in for the packs of the app
+            let can_fall_back = source == Some(PackSource::Manifest);
+            let other_packs = packs
+                .iter()
+                .any(|pack| pack.source == PackSource::Manifest && Some(&pack.id) != pack_id.as_ref());
+            html! {
+                <div class="error-container">
+                    if let Some(error) = error {
+                        <h2>{ error.title() }</h2>
+                        <p class="loading-error">{ error.to_string() }</p>
+                        if error.is_retryable() {
+                            <p>{ format!("Le chargement a échoué après {} tentatives.", LOAD_ATTEMPTS) }</p>
+                        }
+                        <div class="error-actions">
+                            <button class="submit-btn" onclick={on_reload_bank.clone()}>
+                                { "Réessayer" }
+                            </button>
+                            if can_fall_back {
+                                <button class="skip-btn" onclick={on_play_fallback}>
+                                    { "Jouer les questions de secours" }
+                                </button>
+                            }
+                            if other_packs {
+                                <button class="skip-btn" onclick={on_leave_bank}>
+                                    { "Choisir un autre thème" }
+                                </button>
+                            }
+                        </div>
+                    }
+                </div>
+            }
+        },
+        Route::Shared => html! {
+            <SharedResultPage
+                loaded_pack={(*loaded_pack).clone().unwrap_or_default()}
+                packs={(*packs).clone()}
+                on_load_pack={on_select_pack.clone()}
+                on_play={on_play_shared}
+            />
+        },
+        Route::Challenge => html! {
+            <ChallengePage
+                questions={(*questions).clone()}
+                loaded_pack={(*loaded_pack).clone().unwrap_or_default()}
+                packs={(*packs).clone()}
+                on_load_pack={on_select_pack.clone()}
+                on_accept={on_accept_challenge}
+            />
+        },
+        Route::Home => {
+            // A quiz left through the browser history is still in memory, one interrupted by a reload is offered back
+            let unfinished = if !session.is_empty() && !session.finished {
+                Some((session.answered_count(), session.len()))
+            } else {
+                resumable.as_ref().map(|saved| (saved.answered_count(), saved.question_ids.len()))
+            };
+            html! {
+                <>
+                    {
+                        match unfinished {
+                            Some((answered, count)) => html! {
+                                <div class="resume-container">
+                                    <h2>{ "Quiz en cours" }</h2>
+                                    <p>{ format!("Vous avez déjà répondu à {} question(s) sur {}. Voulez-vous reprendre où vous en étiez ?", answered, count) }</p>
+                                    <div class="resume-actions">
+                                        <button class="submit-btn" onclick={on_resume}>
+                                            { "Reprendre" }
+                                        </button>
+                                    </div>
+                                </div>
+                            },
+                            None => html! {},
+                        }
+                    }
+                    if let Some(error) = &*loading_error {
+                        <div class="loading-error">
+                            <p>{ error.to_string() }{ " Des questions de secours sont utilisées." }</p>
+                            <button class="skip-btn" onclick={on_reload_bank}>
+                                { "Réessayer" }
+                            </button>
+                        </div>
+                    }
+                    <BankReport issues={(*bank_issues).clone()} />
+                    <StartScreen
+                        quiz_size={QUIZ_SIZE}
+                        survival_best={*survival_best}
+                        on_start={on_start}
+                        packs={(*packs).clone()}
+                        selected_pack={(*pack_id).clone().unwrap_or_default()}
+                        on_select_pack={on_select_pack}
+                    />
+                    <BankImport on_import={on_import} />
+                </>
+            }
+        },
+        Route::Quiz if session.is_empty() || session.finished => {
+            html! {
+                <div class="error-container">
+                    <p>{ "Aucun quiz en cours." }</p>
+                    <Link<Route> to={Route::Home}>{ "Choisir un mode de jeu" }</Link<Route>>
+                </div>
+            }
+        },
+        Route::Quiz => {
+            let q = session.current_question();
+            let selected = session.current_progress().answer;
+            let submitted = session.current_progress().submitted;
+            let statuses = (0..session.len()).map(|i| session.status(i)).collect::<Vec<_>>();
+            // Survival games are played strictly in order, without skipping
+            let can_skip = session.total().is_some();
+            
+            html! {
+                <>
+                    {
+                        if can_skip {
+                            html! {
+                                <QuestionNavigator
+                                    statuses={statuses}
+                                    current={session.current}
+                                    on_jump={on_jump}
+                                />
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    
+                    <QuestionComponent 
+                        question={q.clone()} 
+                        selected_option={selected}
+                        is_submitted={submitted}
+                        on_select={on_option_select}
+                        removed_options={session.current_progress().removed_options.clone()}
+                        show_hint={session.current_progress().hint_shown}
+                        confidence={session.current_progress().confidence}
+                        on_confidence={session.uses_confidence().then_some(on_confidence)}
+                        tried_options={session.current_progress().wrong_attempts.clone()}
+                        focus_ref={question_ref.clone()}
+                        announce={true}
+                    />
+                    
+                    if !submitted {
+                        <div class="lifelines">
+                            <button
+                                class="lifeline-btn"
+                                onclick={on_fifty_fifty}
+                                disabled={!session.can_use_fifty_fifty()}
+                            >
+                                { format!("50/50 ({})", session.fifty_fifty_left()) }
+                            </button>
+                            if session.has_hints() {
+                                <button
+                                    class="lifeline-btn"
+                                    onclick={on_hint}
+                                    disabled={!session.can_use_hint()}
+                                    title={if q.hint.is_none() { "Pas d'indice pour cette question" } else { "" }}
+                                >
+                                    { format!("💡 Indice ({})", session.hints_left()) }
+                                </button>
+                            }
+                        </div>
+                    }
+                    
+                    <div class="quiz-controls">
+                        {
+                            if !submitted {
+                                html! {
+                                    <>
+                                        if can_skip && session.is_last_pending() {
+                                            <button class="skip-btn" onclick={on_finish_unanswered}>
+                                                { "Terminer sans répondre" }
+                                            </button>
+                                        } else if can_skip {
+                                            <button class="skip-btn" onclick={on_skip}>
+                                                { "Passer" }
+                                            </button>
+                                            <button class="flag-btn" onclick={on_flag}>
+                                                { "Marquer pour révision" }
+                                            </button>
+                                        }
+                                        <button 
+                                            class="submit-btn"
+                                            onclick={on_submit.reform(|_| ())}
+                                            disabled={!session.can_submit()}
+                                        >
+                                            { "Soumettre" }
+                                        </button>
+                                    </>
+                                }
+                            } else {
+                                html! {
+                                    <button 
+                                        ref={next_ref.clone()}
+                                        class="next-btn"
+                                        onclick={on_next.reform(|_| ())}
+                                    >
+                                        { 
+                                            if session.has_next() {
+                                                "Question Suivante"
+                                            } else {
+                                                "Voir les Résultats"
+                                            }
+                                        }
+                                    </button>
+                                }
+                            }
+                        }
+                    </div>
+                </>
+            }
+        },
+        Route::Results | Route::Review if !session.finished => {
+            html! {
+                <div class="error-container">
+                    <p>{ "Aucun résultat à afficher pour le moment." }</p>
+                    <Link<Route> to={Route::Home}>{ "Commencer un quiz" }</Link<Route>>
+                </div>
+            }
+        },
+        Route::Results => {
+            html! {
+                <ResultsScreen
+                    session={(*session).clone()}
+                    survival_best={*survival_best}
+                    new_record={*new_record}
+                    challenge={Challenge::from_session(&session, &questions)}
+                    quiz_title={quiz_title}
+                    on_retry={on_retry}
+                    retry_ref={retry_ref.clone()}
+                />
+            }
+        },
+        Route::Review => {
+            html! {
+                <>
+                    <ResultsReview session={(*session).clone()} />
+                    <div class="result-links">
+                        <Link<Route> to={Route::Results} classes="skip-btn">{ "Retour aux résultats" }</Link<Route>>
+                    </div>
+                </>
+            }
+        },
+    };
+    
+    let show_progress = matches!(route, Route::Quiz | Route::Results) && !*loading && !session.is_empty();
     
     html! {
         <div class="app-container">
             <div class="app-header">
                 <h1>{ "Quiz de Culture Générale" }</h1>
                 <p>{ "Testez vos connaissances avec ces questions variées" }</p>
+                <nav class="app-nav" aria-label="Navigation principale">
+                    <Link<Route> to={Route::Home}>{ "Accueil" }</Link<Route>>
+                    <Link<Route> to={Route::Stats}>{ "Statistiques" }</Link<Route>>
+                    <Link<Route> to={Route::Settings}>{ "Paramètres" }</Link<Route>>
+                </nav>
             </div>
             
-            <div class="progress-info">
-                <span>{ format!("Question {} sur {}", current_question_index + 1, QUIZ_SIZE) }</span>
-                <span>{ format!("{}%", ((current_question_index + 1) as f32 / QUIZ_SIZE as f32 * 100.0) as usize) }</span>
-            </div>
-            <div class="progress-bar">
-                <div class="progress" style={format!("width: {}%", (current_question_index + 1) as f32 / QUIZ_SIZE as f32 * 100.0)}></div>
-            </div>
+            if *bank_update {
+                <div class="update-banner" role="status">
+                    <p>{ "Une nouvelle version des questions est disponible." }</p>
+                    <div class="update-actions">
+                        <button class="submit-btn" onclick={on_reload_page}>{ "Recharger" }</button>
+                        <button class="skip-btn" onclick={on_dismiss_update}>{ "Plus tard" }</button>
+                    </div>
+                </div>
+            }
             
             {
-                match *quiz_state {
-                    QuizState::Loading => {
+                match (show_progress, session.total()) {
+                    (true, Some(total)) => {
+                        let progress = session.answered_count() as f32 / total as f32 * 100.0;
                         html! {
-                            <div class="loading-container">
-                                <div class="loading-spinner"></div>
-                                <p>{ "Chargement des questions..." }</p>
-                                {
-                                    if *loading_error {
-                                        html! {
-                                            <p class="loading-error">
-                                                { "Un problème est survenu lors du chargement des questions externes. " }
-                                                { "Des questions de secours seront utilisées." }
-                                            </p>
-                                        }
-                                    } else {
-                                        html! {}
-                                    }
-                                }
-                            </div>
-                        }
-                    },
-                    QuizState::InProgress => {
-                        if random_questions.is_empty() {
-                            html! {
-                                <div class="error-container">
-                                    <p>{ "Impossible de charger les questions. Veuillez rafraîchir la page." }</p>
+                            <>
+                                <div class="progress-info">
+                                    <span>{ format!("Question {} sur {}", session.current + 1, total) }</span>
+                                    <span>{ format!("{}%", progress as usize) }</span>
                                 </div>
-                            }
-                        } else {
-                            let q = &random_questions[current_question_index];
-                            let selected = (*selected_answers)[current_question_index];
-                            let submitted = *is_submitted;
-                            
-                            html! {
-                                <>
-                                    <QuestionComponent 
-                                        question={q.clone()} 
-                                        selected_option={selected}
-                                        is_submitted={submitted}
-                                        on_select={on_option_select}
-                                    />
-                                    
-                                    <div class="quiz-controls">
-                                        {
-                                            if !submitted {
-                                                html! {
-                                                    <button 
-                                                        class="submit-btn"
-                                                        onclick={on_submit}
-                                                        disabled={selected.is_none()}
-                                                    >
-                                                        { "Soumettre" }
-                                                    </button>
-                                                }
-                                            } else {
-                                                html! {
-                                                    <button 
-                                                        class="next-btn"
-                                                        onclick={on_next}
-                                                    >
-                                                        { 
-                                                            if current_question_index < QUIZ_SIZE - 1 {
-                                                                "Question Suivante"
-                                                            } else {
-                                                                "Voir les Résultats"
-                                                            }
-                                                        }
-                                                    </button>
-                                                }
-                                            }
-                                        }
-                                    </div>
-                                </>
-                            }
+                                <div
+                                    class="progress-bar"
+                                    role="progressbar"
+                                    aria-label="Progression du quiz"
+                                    aria-valuemin="0"
+                                    aria-valuemax="100"
+                                    aria-valuenow={(progress as usize).to_string()}
+                                >
+                                    <div class="progress" style={format!("width: {}%", progress)}></div>
+                                </div>
+                            </>
                         }
                     },
-                    QuizState::Completed => {
-                        let mut score = 0;
-                        for (i, answer) in (*selected_answers).iter().enumerate() {
-                            if let Some(selected) = answer {
-                                if *selected == random_questions[i].correct_answer_index {
-                                    score += 1;
-                                }
-                            }
-                        }
-                        let percentage = (score as f32 / QUIZ_SIZE as f32 * 100.0) as usize;
-                        
-                        // Choose emoji based on score
-                        let (emoji, message) = match percentage {
-                            90..=100 => ("🏆", "Excellent! Vous êtes un expert!"),
-                            70..=89 => ("🎉", "Très bien! Vous avez d'excellentes connaissances!"),
-                            50..=69 => ("👍", "Bien! Vous avez de bonnes connaissances."),
-                            30..=49 => ("🤔", "Pas mal. Continuez à apprendre!"),
-                            _ => ("📚", "Continuez à apprendre, vous progressez!"),
-                        };
-                        
+                    (true, None) => {
+                        let lives = session.lives_left().unwrap_or_default();
                         html! {
-                            <div class="result-container">
-                                <h2>{ "Quiz Terminé!" }</h2>
-                                <div class="result-emoji">{ emoji }</div>
-                                <div class="score">{ score } <span>{ format!("/{}", QUIZ_SIZE) }</span></div>
-                                <p>{ format!("Vous avez obtenu {}% de bonnes réponses", percentage) }</p>
-                                <p style="margin-top: 1rem; color: var(--neutral-color);">{ message }</p>
-                                
-                                <button class="retry-btn" onclick={on_retry}>
-                                    { "Recommencer" }
-                                </button>
-                                
-                                { if percentage >= 70 {
-                                    // Create confetti effect for high scores
-                                    (0..20).map(|i| {
-                                        let left = format!("{}%", i * 5);
-                                        let delay = format!("{}s", (i as f32 * 0.1) % 5.0);
-                                        html! {
-                                            <div class="confetti" style={format!("left: {}; animation-delay: {}", left, delay)}></div>
-                                        }
-                                    }).collect::<Html>()
-                                } else {
-                                    html! {}
-                                }}
+                            <div class="progress-info">
+                                <span>{ format!("Question {}", session.current + 1) }</span>
+                                <span class="lives" aria-label={format!("{} vie(s) restante(s)", lives)}>
+                                    { format!("{}{}", "❤️".repeat(lives), "🖤".repeat(SURVIVAL_LIVES - lives)) }
+                                </span>
                             </div>
                         }
-                    }
+                    },
+                    _ => html! {},
                 }
             }
+            
+            { page }
+            
+            <button class="shortcut-toggle" onclick={on_toggle_help.reform(|_| ())} title="Raccourcis clavier (?)">
+                { "⌨ Raccourcis clavier" }
+            </button>
+            
+            if *show_shortcuts {
+                <ShortcutHelp on_close={on_toggle_help.clone()} />
+            }
         </div>
     }
 }
 
+// The router lives above the app so that the app itself can follow the current route
+#[function_component(Root)]
+fn root() -> Html {
+    // Routes are resolved below the public URL the app is deployed under (see Trunk.toml)
+    let basename = use_memo((), |_| {
+        // Called on every link and fetch, the base URL is only logged once
+        let base = get_base_url();
+        console_log(&format!("Using base URL {}", base));
+        let base = base.trim_end_matches('/');
+        (!base.is_empty()).then(|| AttrValue::from(base.to_string()))
+    });
+    
+    html! {
+        <BrowserRouter basename={(*basename).clone()}>
+            <App />
+        </BrowserRouter>
+    }
+}
+
 fn main() {
-    yew::Renderer::<App>::new().render();
+    offline::register_service_worker();
+    yew::Renderer::<Root>::new().render();
 }
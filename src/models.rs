@@ -2,49 +2,74 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::{JsCast, prelude::*};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Request, RequestInit, RequestMode, Response};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use crate::csv_rows::{parse_rows, BankIssue};
+use crate::error::LoadError;
+use crate::formats::{parse_bank, BankFormat, QuestionBank};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Question {
+    pub id: usize,
     pub text: String,
     pub options: Vec<String>,
     pub correct_answer_index: usize,
+    #[serde(default)]
+    pub explanation: Option<String>,
+    #[serde(default)]
+    pub hint: Option<String>,
+    #[serde(default)]
+    pub media: Option<Media>,
 }
 
-// Private struct for CSV deserialization
-#[derive(Clone, Debug, Deserialize)]
-struct QuestionRecord {
+// Image, sound or video shown with a question; only JSON banks can attach one
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    pub kind: MediaKind,
+    pub url: String,
+    // Text alternative, read to screen reader users
     #[serde(default)]
-    #[allow(dead_code)]
-    id: usize,
-    question: String,
-    option1: String,
-    option2: String,
-    option3: String,
-    option4: String,
-    correct_answer_index: usize,
+    pub alt: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Audio,
+    Video,
 }
 
 impl Question {
-    pub fn new(text: &str, options: Vec<&str>, correct_answer_index: usize) -> Self {
-        Self {
-            text: text.to_string(),
-            options: options.iter().map(|s| s.to_string()).collect(),
-            correct_answer_index,
+    // The same question with its options shown in the given order, `order[i]` being the
+    // original index of the option displayed at position `i`. Orders come from saved quizzes and
+    // links, so anything but a reordering of every option is refused.
+    pub fn with_option_order(&self, order: &[usize]) -> Option<Self> {
+        let mut sorted = order.to_vec();
+        sorted.sort_unstable();
+        if !sorted.iter().copied().eq(0..self.options.len()) {
+            return None;
         }
+        let correct_answer_index = order.iter().position(|&i| i == self.correct_answer_index)?;
+        Some(Self {
+            options: order.iter().map(|&i| self.options[i].clone()).collect(),
+            correct_answer_index,
+            ..self.clone()
+        })
     }
     
-    // Convert a CSV record to a Question
-    fn from_record(record: QuestionRecord) -> Self {
-        Self {
-            text: record.question,
-            options: vec![record.option1, record.option2, record.option3, record.option4],
-            correct_answer_index: record.correct_answer_index,
-        }
+    // Original index of each displayed option, matched by text against the bank version
+    pub fn option_order(&self, original: &Question) -> Vec<usize> {
+        self.options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| original.options.iter().position(|o| o == option).unwrap_or(i))
+            .collect()
     }
 }
 
 // Get the base URL for the application, handles both development and production
-fn get_base_url() -> String {
+pub fn get_base_url() -> String {
     let window = web_sys::window().expect("should have a window in this context");
     let document = window.document().expect("should have a document on window");
     let location = document.location().expect("document should have a location");
@@ -53,69 +78,125 @@ fn get_base_url() -> String {
     let href = location.href().unwrap_or_default();
     let pathname = location.pathname().unwrap_or_default();
     
-    console_log(&format!("Current location: {}", href));
-    console_log(&format!("Current pathname: {}", pathname));
-    
     // If URL contains github.io/quiz-app/ or pathname starts with /quiz-app/, we're in production
     if href.contains("github.io/quiz-app/") || pathname.starts_with("/quiz-app/") {
-        console_log("Using production base URL: /quiz-app/");
-        return "/quiz-app/".to_string();
+        "/quiz-app/".to_string()
     } else {
-        console_log("Using development base URL: /");
-        return "".to_string();
+        "/".to_string()
     }
 }
 
-// Load questions from CSV file asynchronously
-pub async fn load_questions_from_csv() -> Result<Vec<Question>, JsValue> {
-    // Determine base URL and build the path to CSV file
-    let base_url = get_base_url();
-    let csv_url = format!("{}questions.csv", base_url);
+// The question bank played by default, served next to the app
+pub const DEFAULT_BANK: &str = "questions.csv";
+
+// Load a question bank asynchronously, in whichever format the server or the file name says it is
+pub async fn load_question_bank(url: &str) -> Result<QuestionBank, LoadError> {
+    console_log(&format!("Fetching questions from: {}", url));
     
-    console_log(&format!("Fetching questions from: {}", csv_url));
+    let resp = fetch(url).await?;
+    let content_type = resp.headers().get("content-type").ok().flatten();
+    let format = BankFormat::detect(url, content_type.as_deref());
+    let text = read_text(url, resp).await?;
+    
+    // Parse the bank
+    parse_bank(url, &text, format).inspect_err(|e| console_log(&format!("Error parsing {}: {}", url, e)))
+}
+
+// How many times a bank is requested before giving up, waiting twice as long before each new attempt
+pub const LOAD_ATTEMPTS: u32 = 3;
+const FIRST_RETRY_DELAY_MS: i32 = 1000;
+
+// Load a question bank, trying again after failures that may not last; `on_retry` gets the number of the new attempt
+pub async fn load_question_bank_with_retries(url: &str, on_retry: impl Fn(u32)) -> Result<QuestionBank, LoadError> {
+    let mut attempt = 1;
+    loop {
+        match load_question_bank(url).await {
+            Err(e) if e.is_retryable() && attempt < LOAD_ATTEMPTS => {
+                let delay = FIRST_RETRY_DELAY_MS * 2i32.pow(attempt - 1);
+                console_log(&format!("Attempt {} at loading {} failed, retrying in {} ms", attempt, url, delay));
+                sleep(delay).await;
+                attempt += 1;
+                on_retry(attempt);
+            }
+            result => return result,
+        }
+    }
+}
+
+pub async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let scheduled = web_sys::window()
+            .and_then(|window| window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms).ok());
+        // Without a timer, go on straight away rather than never
+        if scheduled.is_none() {
+            let _ = resolve.call0(&JsValue::NULL);
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+// Fetch a file served alongside the app as text
+pub async fn fetch_text(url: &str) -> Result<String, LoadError> {
+    let resp = fetch(url).await?;
+    read_text(url, resp).await
+}
+
+async fn read_text(url: &str, resp: Response) -> Result<String, LoadError> {
+    let decode_error = || LoadError::Decode { url: url.to_string() };
+    let text = resp.text().map_err(|_| decode_error())?;
+    JsFuture::from(text)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .ok_or_else(decode_error)
+}
+
+async fn fetch(url: &str) -> Result<Response, LoadError> {
+    let network_error = |e: JsValue| {
+        console_log(&format!("Failed to fetch {}: {:?}", url, e));
+        LoadError::Network { url: url.to_string() }
+    };
     
     // Create request
     let opts = RequestInit::new();
     opts.set_method("GET");
     opts.set_mode(RequestMode::Cors);
     
-    let request = Request::new_with_str_and_init(&csv_url, &opts)?;
+    let request = Request::new_with_str_and_init(url, &opts).map_err(network_error)?;
     
     // Fetch request
-    let window = web_sys::window().unwrap();
-    let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-    let resp: Response = resp_value.dyn_into().unwrap();
+    let window = web_sys::window().ok_or_else(|| network_error(JsValue::from_str("no window")))?;
+    let resp_value = JsFuture::from(window.fetch_with_request(&request)).await.map_err(network_error)?;
+    let resp: Response = resp_value.dyn_into().map_err(network_error)?;
     
     if !resp.ok() {
-        console_log(&format!("Failed to load CSV: HTTP status {}", resp.status()));
-        return Err(JsValue::from_str("Failed to load questions.csv"));
+        console_log(&format!("Failed to load {}: HTTP status {}", url, resp.status()));
+        return Err(LoadError::Http {
+            url: url.to_string(),
+            status: resp.status(),
+            status_text: resp.status_text(),
+        });
     }
     
-    // Get text from response
-    let text = JsFuture::from(resp.text()?).await?;
-    let csv_text = text.as_string().unwrap();
-    
-    // Parse CSV
-    match parse_csv_string(&csv_text) {
-        Ok(questions) => Ok(questions),
-        Err(e) => {
-            console_log(&format!("Error parsing CSV: {:?}", e));
-            Err(JsValue::from_str("Failed to parse questions.csv"))
-        }
-    }
+    Ok(resp)
 }
 
-// Parse CSV data from a string
-fn parse_csv_string(csv_data: &str) -> Result<Vec<Question>, csv::Error> {
-    let mut reader = csv::Reader::from_reader(csv_data.as_bytes());
-    let mut questions = Vec::new();
-    
-    for result in reader.deserialize() {
-        let record: QuestionRecord = result?;
-        questions.push(Question::from_record(record));
-    }
-    
-    Ok(questions)
+// Parse CSV data from a string, keeping the valid rows and reporting every problem of the others
+pub fn parse_csv_string(csv_data: &str) -> (Vec<Question>, Vec<BankIssue>) {
+    let (rows, issues) = parse_rows(csv_data);
+    let questions = rows
+        .into_iter()
+        .map(|row| Question {
+            id: row.id,
+            text: row.question,
+            options: row.options,
+            correct_answer_index: row.correct_answer_index,
+            explanation: row.explanation,
+            hint: row.hint,
+            media: None,
+        })
+        .collect();
+    (questions, issues)
 }
 
 // Helper function for logging to console
@@ -123,66 +204,21 @@ pub fn console_log(msg: &str) {
     web_sys::console::log_1(&wasm_bindgen::JsValue::from_str(msg));
 }
 
-// This function returns a fallback list of questions in case CSV loading fails
+// The questions of the bank shipped with the app, compiled in by build.rs so they are always at hand
 pub fn get_fallback_questions() -> Vec<Question> {
-    vec![
-        Question::new(
-            "Dans quelle période préhistorique les premiers outils en pierre taillée ont-ils été utilisés ?",
-            vec![
-                "Le Paléolithique inférieur", 
-                "Le Néolithique", 
-                "Le Mésolithique", 
-                "L'âge du bronze"
-            ],
-            0
-        ),
-        Question::new(
-            "Quel hominidé est associé à la culture moustérienne ?",
-            vec![
-                "Homo habilis", 
-                "Néandertal", 
-                "Homo sapiens", 
-                "Australopithèque"
-            ],
-            1
-        ),
-        Question::new(
-            "Comment appelle-t-on les dessins réalisés sur les parois des grottes, comme à Lascaux ?",
-            vec![
-                "Art rupestre", 
-                "Fresque primitive", 
-                "Peinture paléolithique", 
-                "Pictogramme préhistorique"
-            ],
-            0
-        ),
-        Question::new(
-            "Quelle invention marque le début de la sédentarisation au Néolithique ?",
-            vec![
-                "L'agriculture", 
-                "La roue", 
-                "L'écriture", 
-                "Le feu"
-            ],
-            0
-        ),
-        Question::new(
-            "Quelle ville grecque antique est connue pour avoir inventé la démocratie ?",
-            vec![
-                "Sparte", 
-                "Athènes", 
-                "Corinthe", 
-                "Thèbes"
-            ],
-            1
-        ),
-    ]
-}
-
-// Function to get a random selection of N consecutive questions from loaded questions
-pub fn get_random_question_sequence_from_list(questions: &[Question], count: usize) -> Vec<Question> {
-    use rand::{Rng, SeedableRng};
-    use rand::rngs::SmallRng;
+    include!(concat!(env!("OUT_DIR"), "/fallback_questions.rs"))
+}
+
+// Seeded draws must stay the same across rand versions for share and challenge links to keep working,
+// which `StdRng` does not promise. ChaCha12 is what `StdRng` was when the first links were made.
+fn seeded_rng(seed: u64) -> ChaCha12Rng {
+    ChaCha12Rng::seed_from_u64(seed)
+}
+
+// Function to get a random selection of N consecutive questions from loaded questions.
+// The same seed always draws the same questions from the same bank, so a quiz can be shared.
+pub fn get_random_question_sequence_from_list(questions: &[Question], count: usize, seed: u64) -> Vec<Question> {
+    use rand::Rng;
     
     // Handle the case where we don't have enough questions
     if questions.len() <= count {
@@ -190,8 +226,75 @@ pub fn get_random_question_sequence_from_list(questions: &[Question], count: usi
     }
     
     let max_start_index = questions.len() - count;
-    let mut rng = SmallRng::from_entropy();
+    let mut rng = seeded_rng(seed);
     let start_index = rng.gen_range(0..=max_start_index);
     
     questions[start_index..(start_index + count)].to_vec()
 }
+
+// Function to get the whole list of loaded questions in a random order, drawn from the seed
+pub fn get_shuffled_questions(questions: &[Question], seed: u64) -> Vec<Question> {
+    use rand::seq::SliceRandom;
+    
+    let mut shuffled = questions.to_vec();
+    shuffled.shuffle(&mut seeded_rng(seed));
+    shuffled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question() -> Question {
+        Question {
+            id: 5,
+            text: "Q ?".to_string(),
+            options: vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()],
+            correct_answer_index: 0,
+            explanation: None,
+            hint: None,
+            media: None,
+        }
+    }
+
+    fn numbered(count: usize) -> Vec<Question> {
+        (0..count).map(|id| Question { id, ..question() }).collect()
+    }
+
+    // Existing share and challenge links replay these exact draws
+    #[test]
+    fn seeded_draws_do_not_change() {
+        let bank = numbered(100);
+        let drawn = get_random_question_sequence_from_list(&bank, 10, 42);
+        let shuffled = get_shuffled_questions(&numbered(10), 42);
+        assert_eq!(drawn.iter().map(|q| q.id).collect::<Vec<_>>(), (49..59).collect::<Vec<_>>());
+        assert_eq!(shuffled.iter().map(|q| q.id).collect::<Vec<_>>(), [7, 3, 9, 5, 0, 8, 6, 4, 2, 1]);
+    }
+
+    #[test]
+    fn csv_rows_become_questions_and_bad_rows_are_reported() {
+        let (questions, issues) = parse_csv_string(
+            "id,question,option1,option2,option3,option4,correct_answer_index,hint\n3,Q ?,a,b,c,d,1,Un indice\n4,,a,b,c,d,1,\n",
+        );
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].id, 3);
+        assert_eq!(questions[0].options, ["a", "b", "c", "d"]);
+        assert_eq!(questions[0].hint.as_deref(), Some("Un indice"));
+        assert_eq!(issues, [BankIssue::new(3, Some("question"), "question vide".to_string())]);
+    }
+
+    #[test]
+    fn option_order_reorders_options_and_answer() {
+        let reordered = question().with_option_order(&[2, 0, 3, 1]).unwrap();
+        assert_eq!(reordered.options, ["C", "A", "D", "B"]);
+        assert_eq!(reordered.correct_answer_index, 1);
+        assert_eq!(reordered.option_order(&question()), [2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn option_order_must_use_every_option_once() {
+        assert_eq!(question().with_option_order(&[0, 0, 0, 0]), None);
+        assert_eq!(question().with_option_order(&[0, 1, 2, 4]), None);
+        assert_eq!(question().with_option_order(&[0, 1, 2]), None);
+    }
+}
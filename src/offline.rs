@@ -0,0 +1,61 @@
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{MessageEvent, ServiceWorkerContainer};
+use yew::prelude::*;
+use crate::models::{console_log, get_base_url};
+
+// None outside of secure contexts (plain HTTP), where browsers do not offer service workers
+fn service_worker() -> Option<ServiceWorkerContainer> {
+    let navigator = web_sys::window()?.navigator();
+    js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker"))
+        .unwrap_or(false)
+        .then(|| navigator.service_worker())
+}
+
+// Register static/sw.js, which keeps the app and its banks available offline after the first visit
+pub fn register_service_worker() {
+    let Some(container) = service_worker() else {
+        console_log("Service workers are not available, the app will not work offline");
+        return;
+    };
+    let url = format!("{}sw.js", get_base_url());
+    spawn_local(async move {
+        match JsFuture::from(container.register(&url)).await {
+            Ok(_) => console_log(&format!("Service worker registered from {}", url)),
+            Err(e) => console_log(&format!("Failed to register the service worker: {:?}", e)),
+        }
+    });
+}
+
+// URL of the bank in a message of the service worker telling that a newer version was cached
+fn updated_bank(event: &MessageEvent) -> Option<String> {
+    let data = event.data();
+    let kind = js_sys::Reflect::get(&data, &JsValue::from_str("type")).ok()?.as_string()?;
+    if kind != "bank-updated" {
+        return None;
+    }
+    js_sys::Reflect::get(&data, &JsValue::from_str("url")).ok()?.as_string()
+}
+
+// Called with its URL each time the service worker finds a newer version of a cached bank
+#[hook]
+pub fn use_bank_updates(on_update: Callback<String>) {
+    use_effect_with(on_update, |on_update| {
+        let on_update = on_update.clone();
+        let listener = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(url) = updated_bank(&event) {
+                on_update.emit(url);
+            }
+        });
+        let container = service_worker();
+        if let Some(container) = &container {
+            let _ = container.add_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+        }
+
+        move || {
+            if let Some(container) = &container {
+                let _ = container.remove_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+            }
+        }
+    });
+}
@@ -0,0 +1,188 @@
+use serde::Deserialize;
+use crate::grading::PackGrading;
+use crate::models::{console_log, fetch_text, get_base_url, DEFAULT_BANK};
+
+// A question bank listed in packs.json, loaded only once picked
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Pack {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_language")]
+    pub language: String,
+    // Path of the bank relative to the app, or an absolute URL
+    pub file: String,
+    #[serde(default)]
+    pub question_count: Option<usize>,
+    // Used when the bank brings no grading of its own, grading.json applying when neither does
+    #[serde(default)]
+    pub grading: Option<PackGrading>,
+    #[serde(skip)]
+    pub source: PackSource,
+}
+
+// Where a pack comes from
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PackSource {
+    // Listed in packs.json
+    #[default]
+    Manifest,
+    // Opened with `?bank=<url>`
+    Link,
+    // Imported from a file on the player's device, its questions only live in memory
+    File,
+}
+
+// Pack of the bank shipped with the app when there is no manifest
+const DEFAULT_PACK: &str = "culture-generale";
+
+// Prefix of the IDs of imported packs
+const FILE_PREFIX: &str = "file:";
+
+fn default_language() -> String {
+    "fr".to_string()
+}
+
+#[derive(Deserialize)]
+struct PackManifest {
+    packs: Vec<Pack>,
+}
+
+impl Pack {
+    // A bank opened from a link is identified by its URL, so that links made from its quizzes find it again
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            id: url.to_string(),
+            title: "Banque externe".to_string(),
+            description: url.to_string(),
+            language: default_language(),
+            file: url.to_string(),
+            question_count: None,
+            grading: None,
+            source: PackSource::Link,
+        }
+    }
+
+    pub fn from_file(name: &str, title: Option<&str>, question_count: usize) -> Self {
+        Self {
+            id: format!("{}{}", FILE_PREFIX, name),
+            title: title.unwrap_or(name).to_string(),
+            description: format!("Importé depuis {}", name),
+            language: default_language(),
+            file: name.to_string(),
+            question_count: Some(question_count),
+            grading: None,
+            source: PackSource::File,
+        }
+    }
+
+    // `?bank=` also takes a path relative to the page; one that cannot be resolved is kept as it is,
+    // so loading it fails with an error rather than playing another pack
+    pub fn resolve_url(value: &str) -> String {
+        web_sys::window()
+            .and_then(|window| window.location().href().ok())
+            .and_then(|base| web_sys::Url::new_with_base(value, &base).ok())
+            .map(|url| url.href())
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    pub fn is_url(id: &str) -> bool {
+        id.contains("://")
+    }
+
+    // Quizzes of imported packs cannot be shared, the file stays on the player's device
+    pub fn is_shareable(id: &str) -> bool {
+        !id.starts_with(FILE_PREFIX)
+    }
+
+    pub fn url(&self) -> String {
+        if Pack::is_url(&self.file) {
+            self.file.clone()
+        } else {
+            format!("{}{}", get_base_url(), self.file)
+        }
+    }
+}
+
+// The bank shipped with the app, when there is no manifest
+fn default_packs() -> Vec<Pack> {
+    vec![Pack {
+        id: DEFAULT_PACK.to_string(),
+        title: "Culture générale".to_string(),
+        description: String::new(),
+        language: default_language(),
+        file: DEFAULT_BANK.to_string(),
+        question_count: None,
+        grading: None,
+        source: PackSource::Manifest,
+    }]
+}
+
+// The built-in questions are those of the bank shipped with the app, so they are played as its pack,
+// never as the pack that failed: quizzes saved or shared from them must find the same questions again
+pub fn fallback_pack_id(packs: &[Pack]) -> String {
+    packs
+        .iter()
+        .find(|pack| pack.source == PackSource::Manifest && pack.file == DEFAULT_BANK)
+        .map_or_else(|| DEFAULT_PACK.to_string(), |pack| pack.id.clone())
+}
+
+// Pack a share or challenge link was made with; links made before packs existed are for the default bank
+pub fn linked_pack_id(pack: &str, packs: &[Pack]) -> String {
+    if pack.is_empty() {
+        fallback_pack_id(packs)
+    } else {
+        pack.to_string()
+    }
+}
+
+pub async fn load_packs() -> Vec<Pack> {
+    let url = format!("{}packs.json", get_base_url());
+    let manifest = match fetch_text(&url).await {
+        Ok(text) => serde_json::from_str::<PackManifest>(&text).map_err(|e| format!("{}", e)),
+        Err(e) => Err(e.to_string()),
+    };
+    match manifest {
+        Ok(manifest) if !manifest.packs.is_empty() => manifest.packs,
+        Ok(_) => default_packs(),
+        Err(e) => {
+            console_log(&format!("Using the default question pack ({})", e));
+            default_packs()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listed(manifest: &str) -> Vec<Pack> {
+        serde_json::from_str::<PackManifest>(manifest).unwrap().packs
+    }
+
+    #[test]
+    fn fallback_questions_are_played_as_the_pack_of_the_default_bank() {
+        let packs = listed(
+            r#"{ "packs": [
+                { "id": "sciences", "title": "Sciences", "file": "packs/sciences.toml" },
+                { "id": "general", "title": "Général", "file": "questions.csv" }
+            ] }"#,
+        );
+        assert_eq!(fallback_pack_id(&packs), "general");
+    }
+
+    #[test]
+    fn fallback_questions_never_take_the_id_of_another_pack() {
+        let mut packs = listed(r#"{ "packs": [{ "id": "sciences", "title": "Sciences", "file": "packs/sciences.toml" }] }"#);
+        packs.push(Pack::from_url("https://example.org/questions.csv"));
+        assert_eq!(fallback_pack_id(&packs), DEFAULT_PACK);
+    }
+
+    #[test]
+    fn links_without_a_pack_are_for_the_default_bank() {
+        let packs = listed(r#"{ "packs": [{ "id": "general", "title": "Général", "file": "questions.csv" }] }"#);
+        assert_eq!(linked_pack_id("", &packs), "general");
+        assert_eq!(linked_pack_id("sciences", &packs), "sciences");
+    }
+}
@@ -0,0 +1,27 @@
+use yew_router::prelude::*;
+
+// Pages of the app, relative to the base URL it is served from (see `models::get_base_url`)
+#[derive(Clone, Debug, PartialEq, Routable)]
+pub enum Route {
+    #[at("/")]
+    Home,
+    #[at("/quiz")]
+    Quiz,
+    #[at("/results")]
+    Results,
+    #[at("/review")]
+    Review,
+    // Someone else's result, carried in the query string (see `share::SharedResult`)
+    #[at("/shared")]
+    Shared,
+    // Someone else's exact quiz to replay, carried in the query string (see `share::Challenge`)
+    #[at("/challenge")]
+    Challenge,
+    #[at("/stats")]
+    Stats,
+    #[at("/settings")]
+    Settings,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
@@ -0,0 +1,901 @@
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use crate::clock;
+use crate::grading::Grading;
+use crate::models::Question;
+
+// Lives at the start of a survival game
+pub const SURVIVAL_LIVES: usize = 3;
+
+// How many times each lifeline can be used during a quiz
+pub const FIFTY_FIFTY_LIMIT: usize = 1;
+pub const HINT_LIMIT: usize = 2;
+
+// Points taken off a correct answer for each lifeline used on it, when penalties are on
+pub const LIFELINE_PENALTY: f32 = 0.5;
+
+// How a quiz is played: a fixed set of questions, the same set where wrong answers can be
+// retried, or drawing from the whole bank until the player runs out of lives
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum QuizMode {
+    #[default]
+    Classic,
+    Practice,
+    Survival,
+}
+
+// How sure the player was of an answer, rated before submitting it
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    pub const ALL: [Confidence; 3] = [Confidence::Low, Confidence::Medium, Confidence::High];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Confidence::Low => "Faible",
+            Confidence::Medium => "Moyenne",
+            Confidence::High => "Élevée",
+        }
+    }
+
+    // Certainty-based marking: being sure pays off when right and costs more when wrong
+    pub fn points(self, correct: bool) -> i32 {
+        match (self, correct) {
+            (Confidence::Low, true) => 1,
+            (Confidence::Medium, true) => 2,
+            (Confidence::High, true) => 3,
+            (Confidence::Low, false) => 0,
+            (Confidence::Medium, false) => -2,
+            (Confidence::High, false) => -6,
+        }
+    }
+}
+
+// How often the player was right at one confidence level
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationRow {
+    pub confidence: Confidence,
+    pub answered: usize,
+    pub correct: usize,
+}
+
+// Percentage of right answers needed to pass a quiz that sets no pass mark of its own, unless changed in the settings
+pub const DEFAULT_PASS_MARK: usize = 70;
+
+fn default_pass_mark() -> usize {
+    DEFAULT_PASS_MARK
+}
+
+// Choices made in the settings that change how a quiz is scored
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuizOptions {
+    #[serde(default)]
+    pub penalize_lifelines: bool,
+    #[serde(default)]
+    pub confidence_marking: bool,
+    // Percentage needed to pass, and earn a certificate, when the quiz's grading sets none
+    #[serde(default = "default_pass_mark")]
+    pub pass_mark: usize,
+}
+
+impl Default for QuizOptions {
+    fn default() -> Self {
+        Self {
+            penalize_lifelines: false,
+            confidence_marking: false,
+            pass_mark: DEFAULT_PASS_MARK,
+        }
+    }
+}
+
+// A question the player set aside to come back to before the results
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum QuestionMark {
+    None,
+    Skipped,
+    Flagged,
+}
+
+// What the player has done so far on a single question of the quiz
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestionProgress {
+    pub answer: Option<usize>,
+    pub submitted: bool,
+    pub mark: QuestionMark,
+    // Time spent on the question before submitting, in milliseconds
+    pub time_ms: f64,
+    // Wrong options taken away by the 50/50 lifeline
+    #[serde(default)]
+    pub removed_options: Vec<usize>,
+    #[serde(default)]
+    pub hint_shown: bool,
+    #[serde(default)]
+    pub confidence: Option<Confidence>,
+    // Options already tried and found wrong, in practice mode
+    #[serde(default)]
+    pub wrong_attempts: Vec<usize>,
+}
+
+impl QuestionProgress {
+    pub fn lifelines_used(&self) -> usize {
+        usize::from(!self.removed_options.is_empty()) + usize::from(self.hint_shown)
+    }
+
+    // Submissions made, counting the right one once it was found
+    pub fn attempts(&self) -> usize {
+        self.wrong_attempts.len() + usize::from(self.submitted)
+    }
+
+    // The answer the player first committed to, which is what practice mode scores
+    pub fn first_answer(&self) -> Option<usize> {
+        self.wrong_attempts
+            .first()
+            .copied()
+            .or(if self.submitted { self.answer } else { None })
+    }
+}
+
+impl QuestionProgress {
+    fn new() -> Self {
+        Self {
+            answer: None,
+            submitted: false,
+            mark: QuestionMark::None,
+            time_ms: 0.0,
+            removed_options: Vec::new(),
+            hint_shown: false,
+            confidence: None,
+            wrong_attempts: Vec::new(),
+        }
+    }
+}
+
+// State of a question as shown in the navigator strip
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuestionStatus {
+    Pending,
+    Answered,
+    Skipped,
+    Flagged,
+}
+
+// Another player's answers to the same questions, from a challenge link
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Opponent {
+    pub name: String,
+    // First answer given to each question, in the order its options are shown
+    pub answers: Vec<Option<usize>>,
+}
+
+// What gets persisted of a quiz in progress, so it can be resumed after a reload.
+// Questions are stored by ID and looked up again in the bank when resuming.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedQuiz {
+    // Quizzes saved before survival mode existed are classic ones
+    #[serde(default)]
+    pub mode: QuizMode,
+    #[serde(default)]
+    pub options: QuizOptions,
+    #[serde(default)]
+    pub pack: String,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub opponent: Option<Opponent>,
+    #[serde(default)]
+    pub grading: Grading,
+    pub question_ids: Vec<usize>,
+    pub option_orders: Vec<Vec<usize>>,
+    pub progress: Vec<QuestionProgress>,
+    pub current: usize,
+    // Time already spent on the current question when the quiz was saved
+    pub current_elapsed_ms: f64,
+}
+
+impl SavedQuiz {
+    pub fn answered_count(&self) -> usize {
+        self.progress.iter().filter(|p| p.submitted).count()
+    }
+}
+
+// The quiz being played: the drawn questions and the player's progress on each of them
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuizSession {
+    pub mode: QuizMode,
+    pub options: QuizOptions,
+    // ID of the question pack the questions come from
+    pub pack: String,
+    // The questions were drawn from the bank with this seed, drawing again with it gives the same quiz
+    pub seed: u64,
+    // Set when playing someone else's quiz from a challenge link
+    pub opponent: Option<Opponent>,
+    pub grading: Grading,
+    pub questions: Vec<Question>,
+    pub progress: Vec<QuestionProgress>,
+    pub current: usize,
+    // When the player arrived on the current question
+    pub question_started_at: f64,
+    // Set once every question has been dealt with and the results are shown
+    pub finished: bool,
+}
+
+impl QuizSession {
+    pub fn new(questions: Vec<Question>) -> Self {
+        Self::with_mode(QuizMode::Classic, questions)
+    }
+
+    // Same as a classic quiz, but wrong answers can be retried until the right one is found
+    pub fn practice(questions: Vec<Question>) -> Self {
+        Self::with_mode(QuizMode::Practice, questions)
+    }
+
+    // A survival game deals out the whole bank, already shuffled
+    pub fn survival(shuffled_bank: Vec<Question>) -> Self {
+        Self::with_mode(QuizMode::Survival, shuffled_bank)
+    }
+
+    fn with_mode(mode: QuizMode, questions: Vec<Question>) -> Self {
+        let progress = questions.iter().map(|_| QuestionProgress::new()).collect();
+        Self {
+            mode,
+            options: QuizOptions::default(),
+            pack: String::new(),
+            seed: 0,
+            opponent: None,
+            grading: Grading::default(),
+            questions,
+            progress,
+            current: 0,
+            question_started_at: clock::now(),
+            finished: false,
+        }
+    }
+
+    pub fn to_saved(&self, bank: &[Question]) -> SavedQuiz {
+        SavedQuiz {
+            mode: self.mode,
+            options: self.options.clone(),
+            pack: self.pack.clone(),
+            seed: self.seed,
+            opponent: self.opponent.clone(),
+            grading: self.grading.clone(),
+            question_ids: self.questions.iter().map(|q| q.id).collect(),
+            option_orders: self
+                .questions
+                .iter()
+                .map(|q| match bank.iter().find(|b| b.id == q.id) {
+                    Some(original) => q.option_order(original),
+                    None => (0..q.options.len()).collect(),
+                })
+                .collect(),
+            progress: self.progress.clone(),
+            current: self.current,
+            current_elapsed_ms: clock::now() - self.question_started_at,
+        }
+    }
+
+    // Rebuild a saved quiz from the bank; None if it no longer matches the loaded questions
+    pub fn from_saved(saved: SavedQuiz, bank: &[Question]) -> Option<Self> {
+        let count = saved.question_ids.len();
+        if count == 0
+            || saved.option_orders.len() != count
+            || saved.progress.len() != count
+            || saved.current >= count
+        {
+            return None;
+        }
+
+        let questions = saved
+            .question_ids
+            .iter()
+            .zip(&saved.option_orders)
+            .map(|(id, order)| bank.iter().find(|q| q.id == *id)?.with_option_order(order))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            mode: saved.mode,
+            options: saved.options,
+            pack: saved.pack,
+            seed: saved.seed,
+            opponent: saved.opponent,
+            grading: saved.grading,
+            questions,
+            progress: saved.progress,
+            current: saved.current,
+            question_started_at: clock::now() - saved.current_elapsed_ms,
+            finished: false,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    // Number of questions in the quiz, None when it goes on until the player runs out of lives
+    pub fn total(&self) -> Option<usize> {
+        match self.mode {
+            QuizMode::Classic | QuizMode::Practice => Some(self.len()),
+            QuizMode::Survival => None,
+        }
+    }
+
+    pub fn lives_left(&self) -> Option<usize> {
+        match self.mode {
+            QuizMode::Classic | QuizMode::Practice => None,
+            QuizMode::Survival => {
+                let mistakes = (0..self.len())
+                    .filter(|&i| self.progress[i].submitted && !self.is_correct(i))
+                    .count();
+                Some(SURVIVAL_LIVES.saturating_sub(mistakes))
+            }
+        }
+    }
+
+    pub fn current_question(&self) -> &Question {
+        &self.questions[self.current]
+    }
+
+    pub fn current_progress(&self) -> &QuestionProgress {
+        &self.progress[self.current]
+    }
+
+    pub fn status(&self, index: usize) -> QuestionStatus {
+        let progress = &self.progress[index];
+        if progress.submitted {
+            return QuestionStatus::Answered;
+        }
+        match progress.mark {
+            QuestionMark::None => QuestionStatus::Pending,
+            QuestionMark::Skipped => QuestionStatus::Skipped,
+            QuestionMark::Flagged => QuestionStatus::Flagged,
+        }
+    }
+
+    // In practice mode only answers found on the first try count as correct
+    pub fn is_correct(&self, index: usize) -> bool {
+        let progress = &self.progress[index];
+        progress.submitted
+            && progress.answer == Some(self.questions[index].correct_answer_index)
+            && progress.wrong_attempts.is_empty()
+    }
+
+    // A survival game deals the whole bank, only the questions actually played count in its results
+    pub fn played(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.total().is_some() || self.progress[i].submitted)
+            .collect()
+    }
+
+    // Whether the opponent of a challenge got the question right the first time, scored like the player
+    pub fn opponent_is_correct(&self, index: usize) -> Option<bool> {
+        let answer = self.opponent.as_ref()?.answers.get(index).copied()?;
+        Some(answer == Some(self.questions[index].correct_answer_index))
+    }
+
+    pub fn opponent_score(&self) -> Option<usize> {
+        self.opponent.as_ref()?;
+        Some((0..self.len()).filter(|&i| self.opponent_is_correct(i) == Some(true)).count())
+    }
+
+    pub fn answered_count(&self) -> usize {
+        self.progress.iter().filter(|p| p.submitted).count()
+    }
+
+    pub fn score(&self) -> usize {
+        (0..self.len()).filter(|&i| self.is_correct(i)).count()
+    }
+
+    pub fn percentage(&self) -> usize {
+        (self.score() * 100).checked_div(self.len()).unwrap_or(0)
+    }
+
+    pub fn pass_mark(&self) -> usize {
+        self.grading.pass_mark.unwrap_or(self.options.pass_mark)
+    }
+
+    // Survival games have no fixed length to pass
+    pub fn passed(&self) -> bool {
+        self.total().is_some() && self.percentage() >= self.pass_mark()
+    }
+
+    // Lifeline penalties only apply to classic quizzes
+    pub fn penalizes_lifelines(&self) -> bool {
+        self.mode == QuizMode::Classic && self.options.penalize_lifelines
+    }
+
+    // Score with lifeline penalties applied
+    pub fn points(&self) -> f32 {
+        let penalize = self.penalizes_lifelines();
+        (0..self.len())
+            .filter(|&i| self.is_correct(i))
+            .map(|i| {
+                let penalty = if penalize {
+                    self.progress[i].lifelines_used() as f32 * LIFELINE_PENALTY
+                } else {
+                    0.0
+                };
+                (1.0 - penalty).max(0.0)
+            })
+            .sum()
+    }
+
+    // Confidence is only asked for in classic quizzes
+    pub fn uses_confidence(&self) -> bool {
+        self.mode == QuizMode::Classic && self.options.confidence_marking
+    }
+
+    pub fn confidence_score(&self) -> i32 {
+        (0..self.len())
+            .filter_map(|i| {
+                let progress = &self.progress[i];
+                match progress.confidence {
+                    Some(confidence) if progress.submitted => Some(confidence.points(self.is_correct(i))),
+                    _ => None,
+                }
+            })
+            .sum()
+    }
+
+    pub fn max_confidence_score(&self) -> i32 {
+        Confidence::High.points(true) * self.len() as i32
+    }
+
+    pub fn calibration(&self) -> Vec<CalibrationRow> {
+        Confidence::ALL
+            .iter()
+            .map(|&confidence| {
+                let rated = (0..self.len())
+                    .filter(|&i| self.progress[i].submitted && self.progress[i].confidence == Some(confidence))
+                    .collect::<Vec<_>>();
+                CalibrationRow {
+                    confidence,
+                    answered: rated.len(),
+                    correct: rated.iter().filter(|&&i| self.is_correct(i)).count(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn can_submit(&self) -> bool {
+        let progress = self.current_progress();
+        progress.answer.is_some() && (!self.uses_confidence() || progress.confidence.is_some())
+    }
+
+    pub fn fifty_fifty_left(&self) -> usize {
+        let used = self.progress.iter().filter(|p| !p.removed_options.is_empty()).count();
+        FIFTY_FIFTY_LIMIT.saturating_sub(used)
+    }
+
+    pub fn hints_left(&self) -> usize {
+        let used = self.progress.iter().filter(|p| p.hint_shown).count();
+        HINT_LIMIT.saturating_sub(used)
+    }
+
+    pub fn can_use_fifty_fifty(&self) -> bool {
+        let progress = self.current_progress();
+        self.fifty_fifty_left() > 0
+            && !progress.submitted
+            && progress.removed_options.is_empty()
+            && self.untried_wrong_options().len() >= 2
+    }
+
+    fn untried_wrong_options(&self) -> Vec<usize> {
+        let correct = self.current_question().correct_answer_index;
+        let tried = &self.current_progress().wrong_attempts;
+        (0..self.current_question().options.len())
+            .filter(|i| *i != correct && !tried.contains(i))
+            .collect()
+    }
+
+    // Banks without any hint don't offer the hint lifeline at all
+    pub fn has_hints(&self) -> bool {
+        self.questions.iter().any(|q| q.hint.is_some())
+    }
+
+    pub fn can_use_hint(&self) -> bool {
+        let progress = self.current_progress();
+        self.hints_left() > 0
+            && !progress.submitted
+            && !progress.hint_shown
+            && self.current_question().hint.is_some()
+    }
+
+    // Take away two wrong options at random from the current question
+    pub fn use_fifty_fifty(&mut self) {
+        if !self.can_use_fifty_fifty() {
+            return;
+        }
+        let mut wrong = self.untried_wrong_options();
+        wrong.shuffle(&mut SmallRng::from_entropy());
+        wrong.truncate(2);
+
+        let progress = &mut self.progress[self.current];
+        if progress.answer.is_some_and(|answer| wrong.contains(&answer)) {
+            progress.answer = None;
+        }
+        progress.removed_options = wrong;
+    }
+
+    pub fn use_hint(&mut self) {
+        if self.can_use_hint() {
+            self.progress[self.current].hint_shown = true;
+        }
+    }
+
+    pub fn select(&mut self, option_index: usize) {
+        let progress = &mut self.progress[self.current];
+        let unavailable = progress.removed_options.contains(&option_index)
+            || progress.wrong_attempts.contains(&option_index);
+        if !progress.submitted && !unavailable {
+            progress.answer = Some(option_index);
+        }
+    }
+
+    pub fn set_confidence(&mut self, confidence: Confidence) {
+        let progress = &mut self.progress[self.current];
+        if !progress.submitted {
+            progress.confidence = Some(confidence);
+        }
+    }
+
+    pub fn submit(&mut self) {
+        if !self.can_submit() {
+            return;
+        }
+        // A wrong answer in practice mode is greyed out and the player tries again
+        let correct = self.current_question().correct_answer_index;
+        let progress = &mut self.progress[self.current];
+        if self.mode == QuizMode::Practice && progress.answer != Some(correct) {
+            if let Some(wrong) = progress.answer.take() {
+                progress.wrong_attempts.push(wrong);
+            }
+            return;
+        }
+        self.stop_clock();
+        self.progress[self.current].submitted = true;
+    }
+
+    pub fn go_to(&mut self, index: usize) {
+        if index < self.len() {
+            self.move_to(index);
+        }
+    }
+
+    // Set the current question aside and move on; returns false once nothing is left to answer
+    pub fn skip(&mut self) -> bool {
+        self.progress[self.current].mark = QuestionMark::Skipped;
+        self.stop_clock();
+        self.advance()
+    }
+
+    pub fn flag(&mut self) -> bool {
+        self.progress[self.current].mark = QuestionMark::Flagged;
+        self.stop_clock();
+        self.advance()
+    }
+
+    // Whether every other question has been answered, so skipping this one would only come back to it
+    pub fn is_last_pending(&self) -> bool {
+        (0..self.len()).all(|i| i == self.current || self.progress[i].submitted)
+    }
+
+    // End the quiz with the questions left unanswered, once the player asks for it
+    pub fn finish_unanswered(&mut self) {
+        self.stop_clock();
+    }
+
+    // Move to the next question still waiting for an answer; returns false once there is none
+    pub fn advance(&mut self) -> bool {
+        match self.next_pending() {
+            Some(index) => {
+                self.move_to(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.next_pending().is_some()
+    }
+
+    fn move_to(&mut self, index: usize) {
+        self.stop_clock();
+        self.current = index;
+        self.question_started_at = clock::now();
+    }
+
+    // Add the time spent since arriving on the current question, unless it was already answered
+    fn stop_clock(&mut self) {
+        let now = clock::now();
+        let progress = &mut self.progress[self.current];
+        if !progress.submitted {
+            progress.time_ms += now - self.question_started_at;
+        }
+        self.question_started_at = now;
+    }
+
+    fn next_pending(&self) -> Option<usize> {
+        if self.lives_left() == Some(0) {
+            return None;
+        }
+        let count = self.len();
+        // Questions not seen yet come first, in order...
+        (self.current + 1..count)
+            .find(|&i| self.status(i) == QuestionStatus::Pending)
+            // ...then we wrap around to revisit everything skipped or flagged, the current question last
+            .or_else(|| {
+                (1..=count)
+                    .map(|offset| (self.current + offset) % count)
+                    .find(|&i| !self.progress[i].submitted)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock;
+
+    fn questions(count: usize) -> Vec<Question> {
+        (0..count)
+            .map(|id| Question {
+                id,
+                text: format!("Question {}", id),
+                options: vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()],
+                correct_answer_index: 0,
+                explanation: None,
+                hint: None,
+                media: None,
+            })
+            .collect()
+    }
+
+    fn answer(session: &mut QuizSession) {
+        session.select(0);
+        session.submit();
+    }
+
+    #[test]
+    fn hint_lifeline_needs_a_hint() {
+        let mut session = QuizSession::new(questions(2));
+        assert!(!session.has_hints());
+        assert!(!session.can_use_hint());
+        session.questions[1].hint = Some("Un indice".to_string());
+        assert!(session.has_hints());
+        assert!(!session.can_use_hint());
+        session.current = 1;
+        session.use_hint();
+        assert!(session.current_progress().hint_shown);
+        assert_eq!(session.hints_left(), HINT_LIMIT - 1);
+    }
+
+    #[test]
+    fn unseen_questions_come_before_skipped_ones() {
+        let mut session = QuizSession::new(questions(3));
+        assert!(session.skip());
+        assert_eq!(session.current, 1);
+        assert!(session.flag());
+        assert_eq!(session.current, 2);
+        answer(&mut session);
+        assert!(session.advance());
+        assert_eq!(session.current, 0);
+        assert_eq!(session.status(0), QuestionStatus::Skipped);
+        assert_eq!(session.status(1), QuestionStatus::Flagged);
+    }
+
+    #[test]
+    fn skipped_and_flagged_questions_are_revisited_in_order() {
+        let mut session = QuizSession::new(questions(4));
+        session.skip();
+        session.flag();
+        answer(&mut session);
+        session.advance();
+        answer(&mut session);
+        assert!(session.advance());
+        assert_eq!(session.current, 0);
+        answer(&mut session);
+        assert!(session.advance());
+        assert_eq!(session.current, 1);
+        answer(&mut session);
+        assert!(!session.advance());
+    }
+
+    #[test]
+    fn skipping_the_last_question_left_stays_on_it() {
+        let mut session = QuizSession::new(questions(3));
+        answer(&mut session);
+        session.advance();
+        answer(&mut session);
+        session.advance();
+        assert!(session.is_last_pending());
+        assert!(session.skip());
+        assert_eq!(session.current, 2);
+        assert!(session.flag());
+        assert_eq!(session.current, 2);
+    }
+
+    #[test]
+    fn skipping_the_only_unanswered_question_after_a_jump_stays_on_it() {
+        let mut session = QuizSession::new(questions(3));
+        session.skip();
+        answer(&mut session);
+        session.advance();
+        answer(&mut session);
+        session.go_to(0);
+        assert!(session.is_last_pending());
+        assert!(session.skip());
+        assert_eq!(session.current, 0);
+    }
+
+    #[test]
+    fn time_is_counted_until_the_answer_is_submitted() {
+        clock::set_now(1_000.0);
+        let mut session = QuizSession::new(questions(2));
+        clock::set_now(4_000.0);
+        session.skip();
+        clock::set_now(5_000.0);
+        answer(&mut session);
+        clock::set_now(9_000.0);
+        session.advance();
+        clock::set_now(10_000.0);
+        answer(&mut session);
+        assert_eq!(session.progress[0].time_ms, 3_000.0 + 1_000.0);
+        assert_eq!(session.progress[1].time_ms, 1_000.0);
+    }
+
+    #[test]
+    fn resumed_quiz_only_counts_the_time_saved_with_it() {
+        let bank = questions(3);
+        clock::set_now(1_000.0);
+        let session = QuizSession::new(bank.clone());
+        clock::set_now(4_000.0);
+        let saved = session.to_saved(&bank);
+        // Time spent on the home page before picking the quiz up again
+        clock::set_now(60_000.0);
+        let mut resumed = QuizSession::from_saved(saved, &bank).unwrap();
+        clock::set_now(61_000.0);
+        answer(&mut resumed);
+        assert_eq!(resumed.progress[0].time_ms, 3_000.0 + 1_000.0);
+    }
+
+    #[test]
+    fn saved_quiz_comes_back_as_it_was_left() {
+        clock::set_now(0.0);
+        let bank = questions(4);
+        let drawn = vec![bank[2].with_option_order(&[3, 1, 0, 2]).unwrap(), bank[0].clone()];
+        let mut session = QuizSession::new(drawn);
+        session.pack = "sciences".to_string();
+        session.seed = 42;
+        session.select(2);
+        session.submit();
+        session.advance();
+        session.select(1);
+
+        let saved = session.to_saved(&bank);
+        assert_eq!(saved.question_ids, [2, 0]);
+        assert_eq!(saved.option_orders, [vec![3, 1, 0, 2], vec![0, 1, 2, 3]]);
+        let json = serde_json::to_string(&saved).unwrap();
+        let restored = QuizSession::from_saved(serde_json::from_str(&json).unwrap(), &bank).unwrap();
+        assert_eq!(restored, session);
+    }
+
+    #[test]
+    fn saved_quiz_is_dropped_once_the_bank_lost_one_of_its_questions() {
+        let bank = questions(3);
+        let saved = QuizSession::new(bank.clone()).to_saved(&bank);
+        assert!(QuizSession::from_saved(saved.clone(), &bank[..2]).is_none());
+        assert!(QuizSession::from_saved(SavedQuiz { current: 3, ..saved }, &bank).is_none());
+    }
+
+    fn answer_with(session: &mut QuizSession, option: usize) {
+        session.select(option);
+        session.submit();
+    }
+
+    #[test]
+    fn survival_game_ends_with_the_last_life() {
+        let mut session = QuizSession::survival(questions(10));
+        assert_eq!(session.lives_left(), Some(SURVIVAL_LIVES));
+        answer_with(&mut session, 0);
+        for lives in (0..SURVIVAL_LIVES).rev() {
+            assert!(session.advance());
+            answer_with(&mut session, 1);
+            assert_eq!(session.lives_left(), Some(lives));
+        }
+        assert!(!session.has_next());
+        assert!(!session.advance());
+        assert_eq!(session.score(), 1);
+        assert_eq!(session.played(), [0, 1, 2, 3]);
+        assert!(!session.passed());
+    }
+
+    #[test]
+    fn survival_game_ends_when_the_bank_runs_out() {
+        let mut session = QuizSession::survival(questions(2));
+        answer_with(&mut session, 0);
+        session.advance();
+        answer_with(&mut session, 1);
+        assert_eq!(session.lives_left(), Some(SURVIVAL_LIVES - 1));
+        assert!(!session.advance());
+    }
+
+    #[test]
+    fn classic_quizzes_have_no_lives() {
+        let mut session = QuizSession::new(questions(2));
+        answer_with(&mut session, 1);
+        assert_eq!(session.lives_left(), None);
+        assert!(session.advance());
+    }
+
+    #[test]
+    fn confidence_pays_when_right_and_costs_when_wrong() {
+        let points = |correct| Confidence::ALL.map(|confidence| confidence.points(correct));
+        assert_eq!(points(true), [1, 2, 3]);
+        assert_eq!(points(false), [0, -2, -6]);
+    }
+
+    fn rated_session() -> QuizSession {
+        let mut session = QuizSession::new(questions(4));
+        session.options.confidence_marking = true;
+        for (option, confidence) in [(0, Confidence::High), (1, Confidence::High), (0, Confidence::Low), (1, Confidence::Medium)] {
+            session.select(option);
+            assert!(!session.can_submit());
+            session.set_confidence(confidence);
+            session.submit();
+            session.advance();
+        }
+        session
+    }
+
+    #[test]
+    fn confidence_score_adds_the_points_of_every_answer() {
+        let session = rated_session();
+        assert_eq!(session.confidence_score(), 3 - 6 + 1 - 2);
+        assert_eq!(session.max_confidence_score(), 12);
+    }
+
+    #[test]
+    fn calibration_counts_answers_and_right_ones_per_confidence() {
+        let row = |confidence, answered, correct| CalibrationRow { confidence, answered, correct };
+        assert_eq!(
+            rated_session().calibration(),
+            [row(Confidence::Low, 1, 1), row(Confidence::Medium, 1, 0), row(Confidence::High, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn confidence_is_only_asked_for_in_classic_quizzes() {
+        let mut session = QuizSession::practice(questions(1));
+        session.options.confidence_marking = true;
+        assert!(!session.uses_confidence());
+        session.select(0);
+        assert!(session.can_submit());
+    }
+
+    #[test]
+    fn lifeline_penalties_only_apply_to_classic_quizzes() {
+        for (mut session, penalized) in [
+            (QuizSession::new(questions(1)), true),
+            (QuizSession::practice(questions(1)), false),
+            (QuizSession::survival(questions(1)), false),
+        ] {
+            session.options.penalize_lifelines = true;
+            session.progress[0].removed_options = vec![1, 2];
+            answer(&mut session);
+            assert_eq!(session.penalizes_lifelines(), penalized);
+            assert_eq!(session.points(), if penalized { 1.0 - LIFELINE_PENALTY } else { 1.0 });
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use yew_router::Routable;
+use crate::models::{console_log, get_base_url, Question};
+use crate::routes::Route;
+use crate::session::{Opponent, QuizMode, QuizOptions, QuizSession};
+
+// Emojis per line of the share grid
+const GRID_WIDTH: usize = 5;
+
+// What a share link carries in its query string: the sharer's result, and the seed and
+// configuration needed to draw the same quiz again
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedResult {
+    // Links made before packs existed are for the default pack
+    #[serde(default)]
+    pub pack: String,
+    pub seed: u64,
+    pub mode: QuizMode,
+    #[serde(default)]
+    pub penalize_lifelines: bool,
+    #[serde(default)]
+    pub confidence_marking: bool,
+    pub score: usize,
+    pub total: usize,
+    // One character per question played: 1 when right, 0 when wrong and - when left unanswered
+    #[serde(default)]
+    pub answers: String,
+}
+
+impl SharedResult {
+    pub fn from_session(session: &QuizSession) -> Self {
+        let played = session.played();
+        let answers = played
+            .iter()
+            .map(|&i| match (session.progress[i].submitted, session.is_correct(i)) {
+                (true, true) => '1',
+                (true, false) => '0',
+                (false, _) => '-',
+            })
+            .collect();
+
+        Self {
+            pack: session.pack.clone(),
+            seed: session.seed,
+            mode: session.mode,
+            penalize_lifelines: session.options.penalize_lifelines,
+            confidence_marking: session.options.confidence_marking,
+            score: session.score(),
+            total: played.len(),
+            answers,
+        }
+    }
+
+    pub fn options(&self) -> QuizOptions {
+        QuizOptions {
+            penalize_lifelines: self.penalize_lifelines,
+            confidence_marking: self.confidence_marking,
+            ..QuizOptions::default()
+        }
+    }
+
+    pub fn headline(&self) -> String {
+        match self.mode {
+            QuizMode::Survival => format!("Mode survie : {} bonne(s) réponse(s)", self.score),
+            QuizMode::Practice => format!("Entraînement : {}/{} du premier coup", self.score, self.total),
+            QuizMode::Classic => format!("{}/{}", self.score, self.total),
+        }
+    }
+
+    // Right and wrong answers without giving away the questions
+    pub fn emoji_grid(&self) -> String {
+        let cells = self
+            .answers
+            .chars()
+            .map(|c| match c {
+                '1' => "🟩",
+                '0' => "🟥",
+                _ => "⬜",
+            })
+            .collect::<Vec<_>>();
+        cells
+            .chunks(GRID_WIDTH)
+            .map(|row| row.concat())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn url(&self) -> String {
+        app_url(Route::Shared, self)
+    }
+
+    // Message to paste in a chat
+    pub fn text(&self) -> String {
+        format!("Quiz de Culture Générale — {}\n{}\n{}", self.headline(), self.emoji_grid(), self.url())
+    }
+}
+
+// A challenge link replays the sharer's exact quiz and carries their answers for the comparison at the end.
+// Everything lives in the query string, the app is served statically without a backend.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Challenge {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub pack: String,
+    pub mode: QuizMode,
+    #[serde(default)]
+    pub penalize_lifelines: bool,
+    #[serde(default)]
+    pub confidence_marking: bool,
+    // Question IDs with the order their options were shown in, as "12:0.1.2.3,13:2.0.1.3"
+    pub questions: String,
+    // First answer given to each question in the order shown, "-" when left unanswered, as "2,0,-,1"
+    pub answers: String,
+}
+
+impl Challenge {
+    pub fn from_session(session: &QuizSession, bank: &[Question]) -> Self {
+        let played = session.played();
+        let questions = played
+            .iter()
+            .map(|&i| {
+                let question = &session.questions[i];
+                let order = match bank.iter().find(|b| b.id == question.id) {
+                    Some(original) => question.option_order(original),
+                    None => (0..question.options.len()).collect(),
+                };
+                let order = order.iter().map(|o| o.to_string()).collect::<Vec<_>>().join(".");
+                format!("{}:{}", question.id, order)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let answers = played
+            .iter()
+            .map(|&i| match session.progress[i].first_answer() {
+                Some(answer) => answer.to_string(),
+                None => "-".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Self {
+            name: String::new(),
+            pack: session.pack.clone(),
+            mode: session.mode,
+            penalize_lifelines: session.options.penalize_lifelines,
+            confidence_marking: session.options.confidence_marking,
+            questions,
+            answers,
+        }
+    }
+
+    pub fn sender(&self) -> &str {
+        if self.name.trim().is_empty() {
+            "Votre ami"
+        } else {
+            self.name.trim()
+        }
+    }
+
+    // Rebuild the sharer's quiz from the bank; None if the link is malformed or no longer matches the questions
+    pub fn to_session(&self, bank: &[Question]) -> Option<QuizSession> {
+        let questions = self
+            .questions
+            .split(',')
+            .map(|entry| {
+                let (id, order) = entry.split_once(':')?;
+                let id = id.parse::<usize>().ok()?;
+                let order = order
+                    .split('.')
+                    .map(|o| o.parse::<usize>().ok())
+                    .collect::<Option<Vec<_>>>()?;
+                bank.iter().find(|q| q.id == id)?.with_option_order(&order)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let answers = self
+            .answers
+            .split(',')
+            .map(|answer| match answer {
+                "-" => Some(None),
+                answer => answer.parse::<usize>().ok().map(Some),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let answers_fit = answers
+            .iter()
+            .zip(&questions)
+            .all(|(answer, question)| answer.is_none_or(|a| a < question.options.len()));
+        if questions.is_empty() || answers.len() != questions.len() || !answers_fit {
+            return None;
+        }
+
+        let mut session = match self.mode {
+            QuizMode::Classic => QuizSession::new(questions),
+            QuizMode::Practice => QuizSession::practice(questions),
+            QuizMode::Survival => QuizSession::survival(questions),
+        };
+        session.options = QuizOptions {
+            penalize_lifelines: self.penalize_lifelines,
+            confidence_marking: self.confidence_marking,
+            ..QuizOptions::default()
+        };
+        session.opponent = Some(Opponent {
+            name: self.sender().to_string(),
+            answers,
+        });
+        Some(session)
+    }
+
+    pub fn url(&self) -> String {
+        app_url(Route::Challenge, self)
+    }
+}
+
+// Absolute link to a page of the app, with its query string
+fn app_url<T: Serialize>(route: Route, query: &T) -> String {
+    let origin = web_sys::window()
+        .and_then(|window| window.location().origin().ok())
+        .unwrap_or_default();
+    let query = serde_urlencoded::to_string(query).unwrap_or_default();
+    format!("{}{}{}?{}", origin, get_base_url().trim_end_matches('/'), route.to_path(), query)
+}
+
+pub fn copy_to_clipboard(text: String) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let promise = window.navigator().clipboard().write_text(&text);
+    spawn_local(async move {
+        if let Err(e) = JsFuture::from(promise).await {
+            console_log(&format!("Could not copy to the clipboard: {:?}", e));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Confidence;
+
+    fn bank() -> Vec<Question> {
+        (0..4)
+            .map(|id| Question {
+                id,
+                text: format!("Question {}", id),
+                options: ["A", "B", "C", "D"].iter().map(|option| format!("{}{}", option, id)).collect(),
+                correct_answer_index: id % 4,
+                explanation: None,
+                hint: None,
+                media: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn challenge_replays_the_quiz_with_the_sender_answers() {
+        let bank = bank();
+        let drawn = vec![bank[3].with_option_order(&[3, 0, 1, 2]).unwrap(), bank[1].clone()];
+        let mut played = QuizSession::new(drawn.clone());
+        played.pack = "sciences".to_string();
+        played.options.confidence_marking = true;
+        played.select(0);
+        played.set_confidence(Confidence::High);
+        played.submit();
+
+        let mut challenge = Challenge::from_session(&played, &bank);
+        assert_eq!(challenge.questions, "3:3.0.1.2,1:0.1.2.3");
+        assert_eq!(challenge.answers, "0,-");
+        challenge.name = " Alex ".to_string();
+        let link = serde_urlencoded::to_string(&challenge).unwrap();
+        let challenge = serde_urlencoded::from_str::<Challenge>(&link).unwrap();
+
+        let session = challenge.to_session(&bank).unwrap();
+        assert_eq!(session.questions, drawn);
+        assert_eq!(session.mode, QuizMode::Classic);
+        assert!(session.options.confidence_marking);
+        assert_eq!(session.opponent, Some(Opponent { name: "Alex".to_string(), answers: vec![Some(0), None] }));
+        assert_eq!(session.opponent_score(), Some(1));
+    }
+
+    #[test]
+    fn survival_challenge_only_carries_the_questions_played() {
+        let bank = bank();
+        let mut played = QuizSession::survival(bank.clone());
+        played.select(1);
+        played.submit();
+        let challenge = Challenge::from_session(&played, &bank);
+        assert_eq!(challenge.questions, "0:0.1.2.3");
+        assert_eq!(challenge.to_session(&bank).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn challenge_on_other_questions_is_invalid() {
+        let bank = bank();
+        let challenge = Challenge::from_session(&QuizSession::new(bank.clone()), &bank);
+        assert!(challenge.to_session(&bank[..3]).is_none());
+        let broken = Challenge { answers: "0,9,-,-".to_string(), ..challenge };
+        assert!(broken.to_session(&bank).is_none());
+    }
+}
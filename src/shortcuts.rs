@@ -0,0 +1,110 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{HtmlElement, KeyboardEvent};
+use yew::prelude::*;
+
+// What a key press asks the app to do
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shortcut {
+    SelectOption(usize),
+    Confirm,
+    Retry,
+    ToggleHelp,
+    CloseHelp,
+}
+
+// Listed in the help overlay, in the order they are shown
+pub const SHORTCUT_HELP: [(&str, &str); 5] = [
+    ("A–D ou 1–4", "Choisir une réponse"),
+    ("Entrée", "Soumettre, puis passer à la question suivante"),
+    ("R", "Recommencer depuis l'écran des résultats"),
+    ("?", "Afficher ou masquer cette aide"),
+    ("Échap", "Fermer cette aide"),
+];
+
+pub fn shortcut_for(event: &KeyboardEvent) -> Option<Shortcut> {
+    // Leave browser shortcuts such as Ctrl+R alone
+    if event.ctrl_key() || event.meta_key() || event.alt_key() {
+        return None;
+    }
+    let key = event.key();
+    match key.as_str() {
+        "Enter" => Some(Shortcut::Confirm),
+        "?" => Some(Shortcut::ToggleHelp),
+        "Escape" => Some(Shortcut::CloseHelp),
+        "r" | "R" => Some(Shortcut::Retry),
+        "a" | "A" | "1" => Some(Shortcut::SelectOption(0)),
+        "b" | "B" | "2" => Some(Shortcut::SelectOption(1)),
+        "c" | "C" | "3" => Some(Shortcut::SelectOption(2)),
+        "d" | "D" | "4" => Some(Shortcut::SelectOption(3)),
+        _ => None,
+    }
+}
+
+// Whether the key press belongs to an element that handles it itself: text fields take
+// every key, and buttons already turn Enter into a click
+pub fn is_handled_by_target(event: &KeyboardEvent) -> bool {
+    let Some(target) = event.target().and_then(|t| t.dyn_into::<HtmlElement>().ok()) else {
+        return false;
+    };
+    handles_key(&target.tag_name(), target.get_attribute("role").as_deref(), &event.key())
+}
+
+// Options are buttons acting as radios: like native radios, Enter on them submits rather than picking again
+fn handles_key(tag_name: &str, role: Option<&str>, key: &str) -> bool {
+    match tag_name {
+        "INPUT" | "TEXTAREA" | "SELECT" => true,
+        "BUTTON" | "A" => key == "Enter" && role != Some("radio"),
+        _ => false,
+    }
+}
+
+pub fn focus(node_ref: &NodeRef) {
+    if let Some(element) = node_ref.cast::<HtmlElement>() {
+        let _ = element.focus();
+    }
+}
+
+// Listen to key presses on the whole document for as long as the component is mounted
+#[hook]
+pub fn use_document_keydown(on_keydown: Callback<KeyboardEvent>) {
+    use_effect_with(on_keydown, |on_keydown| {
+        let on_keydown = on_keydown.clone();
+        let listener = Closure::<dyn Fn(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+            on_keydown.emit(event)
+        });
+        let document = web_sys::window().and_then(|w| w.document());
+        if let Some(document) = &document {
+            let _ = document.add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+        }
+
+        move || {
+            if let Some(document) = &document {
+                let _ = document.remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter_on_an_option_submits() {
+        assert!(!handles_key("BUTTON", Some("radio"), "Enter"));
+    }
+
+    #[test]
+    fn enter_on_other_buttons_and_links_clicks_them() {
+        assert!(handles_key("BUTTON", None, "Enter"));
+        assert!(handles_key("A", None, "Enter"));
+        assert!(!handles_key("BUTTON", None, "a"));
+    }
+
+    #[test]
+    fn text_fields_take_every_key() {
+        assert!(handles_key("INPUT", None, "a"));
+        assert!(handles_key("TEXTAREA", None, "Enter"));
+        assert!(!handles_key("DIV", None, "Enter"));
+    }
+}
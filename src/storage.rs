@@ -0,0 +1,47 @@
+use serde::{de::DeserializeOwned, Serialize};
+use web_sys::Storage;
+use crate::models::console_log;
+
+// Keys of everything the app keeps in localStorage
+pub const ACTIVE_QUIZ_KEY: &str = "quiz-app.active-quiz";
+pub const SURVIVAL_BEST_KEY: &str = "quiz-app.survival-best";
+pub const LAST_RESULT_KEY: &str = "quiz-app.last-result";
+pub const HISTORY_KEY: &str = "quiz-app.history";
+pub const SETTINGS_KEY: &str = "quiz-app.settings";
+pub const SELECTED_PACK_KEY: &str = "quiz-app.pack";
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+// Read a JSON value from localStorage, None if it is missing or unreadable
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = local_storage()?.get_item(key).ok().flatten()?;
+    match serde_json::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            console_log(&format!("Ignoring unreadable {} in localStorage: {}", key, e));
+            None
+        }
+    }
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    match serde_json::to_string(value) {
+        Ok(raw) => {
+            if storage.set_item(key, &raw).is_err() {
+                console_log(&format!("Could not write {} to localStorage", key));
+            }
+        }
+        Err(e) => console_log(&format!("Could not serialize {}: {}", key, e)),
+    }
+}
+
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
@@ -0,0 +1,189 @@
+// Service worker keeping the quiz playable offline after the first visit.
+// - The page and the bundle built by Trunk (hashed .js, .wasm and .css files) are cached when installing.
+// - Pages are fetched from the network first, so a new deploy is picked up as soon as it is online.
+// - Question banks and the other data files are served from the cache straight away and refreshed in
+//   the background; the app is told when a refreshed bank differs, to offer a reload.
+
+const CACHE = 'quiz-app-v2';
+// Everything is resolved against the folder of this script, `/quiz-app/` once deployed
+const SHELL = new URL('./', self.location).href;
+const DEFAULT_BANK = 'questions.csv';
+const DATA_FILES = [DEFAULT_BANK, 'packs.json', 'grading.json', 'question-bank.schema.json'];
+const INSTALL_FILES = ['manifest.webmanifest', 'icon.svg'];
+const ASSET = /\.(?:js|wasm|css)$/;
+const DATA = /\.(?:csv|json|toml)$/;
+
+function isInstallFile(url) {
+  return INSTALL_FILES.some((file) => url.href === new URL(file, SHELL).href);
+}
+
+// Files of the bundle referenced by the page, whose names change with every build
+function bundleFiles(html) {
+  // Trunk links them in the head and imports them from an inline script
+  const files = [...html.matchAll(/["']([^"'\s]+\.(?:js|wasm|css))["']/g)]
+    .map((match) => new URL(match[1], SHELL))
+    .filter((url) => url.origin === self.location.origin && ASSET.test(url.pathname))
+    .map((url) => url.href);
+  return [...new Set(files)];
+}
+
+// Cache the page with its bundle, and forget the bundles of previous builds
+async function cacheShell(cache, response) {
+  const html = await response.clone().text();
+  const files = bundleFiles(html);
+  await cache.put(SHELL, response);
+  await Promise.all(
+    files.map(async (file) => {
+      if (!(await cache.match(file))) {
+        await cache.add(file);
+      }
+    })
+  );
+  const keys = await cache.keys();
+  await Promise.all(
+    keys
+      .filter((request) => ASSET.test(new URL(request.url).pathname) && !files.includes(request.url))
+      .filter((request) => request.url !== self.location.href)
+      .map((request) => cache.delete(request))
+  );
+}
+
+// The packs listed in the manifest
+async function listedPacks() {
+  try {
+    const response = await fetch(new URL('packs.json', SHELL));
+    return (await response.json()).packs || [];
+  } catch (error) {
+    return [];
+  }
+}
+
+// Files named by the packs, except those served by other sites; a grading written in the manifest names none
+function localFiles(files) {
+  return files.filter((file) => typeof file === 'string' && !file.includes('://'));
+}
+
+self.addEventListener('install', (event) => {
+  event.waitUntil(
+    (async () => {
+      const cache = await caches.open(CACHE);
+      await cacheShell(cache, await fetch(SHELL, { cache: 'no-cache' }));
+      const packs = await listedPacks();
+      const files = [
+        ...DATA_FILES,
+        ...INSTALL_FILES,
+        ...localFiles(packs.map((pack) => pack.file)),
+        ...localFiles(packs.map((pack) => pack.grading)),
+      ].map((file) => new URL(file, SHELL).href);
+      // A missing optional file must not prevent installing
+      await Promise.all(files.map((file) => cache.add(file).catch(() => undefined)));
+      await self.skipWaiting();
+    })()
+  );
+});
+
+self.addEventListener('activate', (event) => {
+  event.waitUntil(
+    (async () => {
+      const names = await caches.keys();
+      await Promise.all(names.filter((name) => name !== CACHE).map((name) => caches.delete(name)));
+      await self.clients.claim();
+    })()
+  );
+});
+
+// Every route of the app is the same page, deep links work offline too
+async function fromNetworkFirst(event) {
+  const cache = await caches.open(CACHE);
+  try {
+    const response = await fetch(event.request);
+    if (response.ok) {
+      event.waitUntil(cacheShell(cache, response.clone()));
+    }
+    return response;
+  } catch (error) {
+    return (await cache.match(SHELL)) || Response.error();
+  }
+}
+
+async function fromCacheFirst(request) {
+  const cache = await caches.open(CACHE);
+  const cached = await cache.match(request);
+  if (cached) {
+    return cached;
+  }
+  const response = await fetch(request);
+  if (response.ok) {
+    await cache.put(request, response.clone());
+  }
+  return response;
+}
+
+// The packs listed in the cached manifest, without going to the network again
+async function cachedPacks(cache) {
+  try {
+    const response = await cache.match(new URL('packs.json', SHELL).href);
+    return (await response.json()).packs || [];
+  } catch (error) {
+    return [];
+  }
+}
+
+// Only a new version of a bank changes what is played: the default bank, the banks of the listed packs and
+// banks linked with `?bank=`. The manifest, the gradings and the schema are refreshed without telling the app.
+async function isBank(cache, url) {
+  const packs = await cachedPacks(cache);
+  const resolve = (file) => new URL(file, SHELL).href;
+  if ([DEFAULT_BANK, ...packs.map((pack) => pack.file)].map(resolve).includes(url)) {
+    return true;
+  }
+  const gradings = packs.map((pack) => pack.grading).filter((grading) => typeof grading === 'string');
+  const others = [...DATA_FILES.filter((file) => file !== DEFAULT_BANK), ...gradings].map(resolve);
+  // Any other data file can only be a linked bank
+  return !others.includes(url);
+}
+
+async function notifyUpdate(url) {
+  const clients = await self.clients.matchAll({ type: 'window' });
+  clients.forEach((client) => client.postMessage({ type: 'bank-updated', url }));
+}
+
+// Banks linked with `?bank=` are kept too, as long as their server allows reading them
+async function refresh(cache, request, cached) {
+  const response = await fetch(request, { cache: 'no-cache' });
+  if (!response.ok || response.type === 'opaque') {
+    return response;
+  }
+  const changed = cached && (await cached.clone().text()) !== (await response.clone().text());
+  if (changed && (await isBank(cache, request.url))) {
+    await notifyUpdate(request.url);
+  }
+  await cache.put(request, response.clone());
+  return response;
+}
+
+async function fromCacheThenRefresh(event) {
+  const cache = await caches.open(CACHE);
+  const cached = await cache.match(event.request);
+  const refreshed = refresh(cache, event.request, cached);
+  if (cached) {
+    event.waitUntil(refreshed.catch(() => undefined));
+    return cached;
+  }
+  return refreshed;
+}
+
+self.addEventListener('fetch', (event) => {
+  const { request } = event;
+  if (request.method !== 'GET') {
+    return;
+  }
+  const url = new URL(request.url);
+  if (request.mode === 'navigate') {
+    event.respondWith(fromNetworkFirst(event));
+  } else if (DATA.test(url.pathname)) {
+    event.respondWith(fromCacheThenRefresh(event));
+  } else if (url.origin === self.location.origin && (ASSET.test(url.pathname) || isInstallFile(url))) {
+    event.respondWith(fromCacheFirst(request));
+  }
+});